@@ -0,0 +1,98 @@
+use std::fs::read_to_string;
+use std::hint::black_box;
+use std::path::Path;
+
+use advent_of_code::{inputs, prelude::*, solver, year_2022, year_2023};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn input(year: u32, day: u32) -> Option<String> {
+    read_to_string(inputs::path(Path::new(inputs::DEFAULT_DIR), year, day)).ok()
+}
+
+// benchmarks parse, part 1 and part 2 of every registered puzzle with an input
+fn solvers(c: &mut Criterion) {
+    for puzzle in solver::puzzles() {
+        let Some(input) = input(puzzle.year, puzzle.day) else {
+            eprintln!("skipping {puzzle}: no input");
+            continue;
+        };
+        let parsed = match puzzle.parse(&input) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("skipping {puzzle}: {e:#}");
+                continue;
+            }
+        };
+
+        let mut g = c.benchmark_group(format!("{}/day_{:02}", puzzle.year, puzzle.day));
+        g.bench_function("parse", |b| b.iter(|| puzzle.parse(black_box(&input))));
+        g.bench_function("part1", |b| b.iter(|| puzzle.part1(black_box(&parsed))));
+        g.bench_function("part2", |b| b.iter(|| puzzle.part2(black_box(&parsed))));
+        g.finish();
+    }
+}
+
+// The naive strategies don't finish on real inputs, so these compare
+// strategies on each puzzle's example instead.
+const EXAMPLE_2022_12: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+const EXAMPLE_2023_08: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+fn strategies(c: &mut Criterion) {
+    use advent_of_code::search;
+    use year_2022::day_12::pathings;
+    use year_2023::day_08;
+
+    let map = year_2022::day_12::Solution::parse(EXAMPLE_2022_12).unwrap();
+    let hill = pathings::Hill::new(&map).unwrap();
+    let start = pathings::find_start(&map);
+    let end = |p: &(usize, usize)| *p == hill.end();
+    let mut g = c.benchmark_group("2022/day_12/shortest_path");
+    g.bench_function("bfs", |b| {
+        b.iter(|| search::bfs(black_box(&hill), [start], end))
+    });
+    g.bench_function("dijkstra", |b| {
+        b.iter(|| search::dijkstra(black_box(&hill), [start], end))
+    });
+    g.bench_function("astar", |b| {
+        b.iter(|| search::astar(black_box(&hill), [start], end))
+    });
+    g.finish();
+
+    let (dirs, map) = day_08::Solution::parse(EXAMPLE_2023_08).unwrap();
+    let starts = || {
+        map.keys()
+            .filter(|n| n.ends_with('A'))
+            .map(|s| s.as_str())
+            .collect()
+    };
+    let mut g = c.benchmark_group("2023/day_08/parallel");
+    g.bench_function("traverse_parallel_dumb", |b| {
+        b.iter(|| day_08::traverse_parallel_dumb(&map, &mut dirs.parse().unwrap(), starts()))
+    });
+    g.bench_function("traverse_parallel", |b| {
+        b.iter(|| day_08::traverse_parallel(&map, &day_08::parse_dirs(&dirs).unwrap(), starts()))
+    });
+    g.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // some days take seconds per iteration, so keep the sample count low
+    config = Criterion::default().sample_size(10);
+    targets = solvers, strategies
+}
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+// Generates a test for every example fixture under fixtures/<year>/<day>/, so
+// that adding a regression case is a matter of adding a file.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let fixtures = root.join("fixtures");
+    println!("cargo:rerun-if-changed={}", fixtures.display());
+
+    let mut tests = String::new();
+    for (year, day, path) in find(&fixtures) {
+        let name = path.file_stem().unwrap().to_string_lossy();
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        let ignore = match ignore_reason(&path) {
+            Some(reason) => format!("#[ignore = {reason:?}]\n"),
+            None => String::new(),
+        };
+
+        writeln!(
+            tests,
+            "#[test]\n{ignore}fn fixture_{year}_{day:02}_{name}() -> crate::prelude::Result<()> {{\n    super::check_file({year}, {day}, std::path::Path::new({:?}))\n}}\n",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("fixture_tests.rs");
+    fs::write(out, tests).unwrap();
+}
+
+// find returns every <year>/<day>/<name>.toml under dir, sorted
+fn find(dir: &Path) -> Vec<(u32, u32, PathBuf)> {
+    let mut found = vec![];
+    for (year, year_dir) in numbered(dir) {
+        for (day, day_dir) in numbered(&year_dir) {
+            for entry in fs::read_dir(&day_dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().map_or(false, |e| e == "toml") {
+                    found.push((year, day, path));
+                }
+            }
+        }
+    }
+
+    found.sort();
+    found
+}
+
+// numbered returns the subdirectories of dir named with a number
+fn numbered(dir: &Path) -> Vec<(u32, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_dir())
+        .map(|p| {
+            let n = p
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.parse().ok())
+                .unwrap_or_else(|| panic!("fixture directory {} isn't a number", p.display()));
+            (n, p)
+        })
+        .collect()
+}
+
+// ignore_reason returns the fixture's `ignore` key, if it has one
+fn ignore_reason(path: &Path) -> Option<String> {
+    let src = fs::read_to_string(path).unwrap();
+    let table: toml::Table = src
+        .parse()
+        .unwrap_or_else(|e| panic!("invalid fixture {}: {e}", path.display()));
+
+    table.get("ignore")?.as_str().map(str::to_string)
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+
+use num::{BigInt, ToPrimitive};
+use serde::{Serialize, Serializer};
+
+use crate::prelude::*;
+
+// Answer is the result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    // only used for values which don't fit in Int, see From<BigInt>
+    BigInt(BigInt),
+    Str(String),
+    // multi-line answers like a rendered screen, one entry per row
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    // returns true if the answer spans more than one line when displayed
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Lines(l) if l.len() > 1)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(i) => write!(f, "{i}"),
+            Answer::BigInt(i) => write!(f, "{i}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+// serializes answers as numbers when they're safe to treat as one, which is
+// when they fit in an i64, and otherwise as their displayed string
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, s: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(i) => match i64::try_from(*i) {
+                Ok(i) => s.serialize_i64(i),
+                Err(_) => s.serialize_str(&i.to_string()),
+            },
+            other => s.serialize_str(&other.to_string()),
+        }
+    }
+}
+
+// parses a recorded answer. Numbers become Int or BigInt and anything spanning
+// more than one line becomes Lines, so that answers compare equal to what the
+// solvers return.
+impl FromStr for Answer {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim_end_matches('\n');
+        if s.contains('\n') {
+            return Ok(Answer::Lines(s.lines().map(str::to_string).collect()));
+        }
+
+        Ok(match s.parse::<BigInt>() {
+            Ok(i) => i.into(),
+            Err(_) => Answer::Str(s.to_string()),
+        })
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(i: $t) -> Self {
+                    Answer::Int(i.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(i: usize) -> Self {
+        Answer::Int(i as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(i: isize) -> Self {
+        Answer::Int(i as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(i: u128) -> Self {
+        BigInt::from(i).into()
+    }
+}
+
+// big integers are narrowed when possible so that equal values compare equal
+impl From<BigInt> for Answer {
+    fn from(i: BigInt) -> Self {
+        match i.to_i128() {
+            Some(i) => Answer::Int(i),
+            None => Answer::BigInt(i),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Lines(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ints() {
+        assert_eq!(Answer::Int(5), 5usize.into());
+        assert_eq!(Answer::Int(-5), (-5i64).into());
+        assert_eq!(Answer::Int(u64::MAX as i128), u64::MAX.into());
+        assert_eq!(Answer::Int(10), BigInt::from(10).into());
+        assert_eq!(Answer::Int(10), 10u128.into());
+
+        let big: BigInt = BigInt::from(u128::MAX) * 2;
+        assert_eq!(Answer::BigInt(big.clone()), big.into());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("1234", Answer::from(1234).to_string());
+        assert_eq!("CMZ", Answer::from("CMZ").to_string());
+
+        let lines = Answer::from(vec!["#..#".to_string(), ".##.".to_string()]);
+        assert!(lines.is_multiline());
+        assert_eq!("#..#\n.##.", lines.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Answer::Int(-12), "-12".parse().unwrap());
+        assert_eq!(Answer::from("CMZ"), "CMZ".parse().unwrap());
+        assert_eq!(
+            Answer::from(vec!["#..#".to_string(), ".##.".to_string()]),
+            "#..#\n.##.\n".parse().unwrap()
+        );
+
+        let big = "1".repeat(50);
+        assert_eq!(
+            Answer::BigInt(big.parse().unwrap()),
+            big.parse::<Answer>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let json = |a: Answer| serde_json::to_string(&a).unwrap();
+
+        assert_eq!("-12", json(Answer::Int(-12)));
+        assert_eq!("\"18446744073709551616\"", json(Answer::Int(1 << 64)));
+        assert_eq!("\"CMZ\"", json("CMZ".into()));
+        assert_eq!(
+            "\"#..#\\n.##.\"",
+            json(vec!["#..#".to_string(), ".##.".to_string()].into())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+#[cfg(test)]
+pub(crate) mod test_server;
+mod verdict;
+
+pub use verdict::{Response, Verdict};
+
+// SITE is the advent of code website, which all requests are made against.
+pub const SITE: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/brhoades/advent-of-code";
+
+// Transport makes authenticated requests against the advent of code site.
+// Paths are relative to the site root, e.g. /2023/day/1/input.
+pub trait Transport {
+    fn get(&self, path: &str) -> Result<String>;
+
+    // post sends a url encoded form
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String>;
+}
+
+// Http is the real transport. It authenticates with a session token taken from
+// the site's session cookie after logging in.
+pub struct Http {
+    base: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Http {
+    pub fn new(session: &str) -> Self {
+        Self::with_base(SITE, session)
+    }
+
+    // with_base sends requests somewhere other than the real site
+    pub fn with_base(base: &str, session: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+}
+
+impl Http {
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        self.agent
+            .request(method, &format!("{}{path}", self.base))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+fn response_body(
+    method: &str,
+    path: &str,
+    resp: Result<ureq::Response, ureq::Error>,
+) -> Result<String> {
+    match resp {
+        Ok(resp) => Ok(resp.into_string()?),
+        Err(ureq::Error::Status(code, resp)) => bail!(
+            "{method} {path} failed with {code}: {}",
+            resp.into_string().unwrap_or_default().trim()
+        ),
+        Err(e) => Err(e).with_context(|| format!("{method} {path} failed")),
+    }
+}
+
+impl Transport for Http {
+    fn get(&self, path: &str) -> Result<String> {
+        response_body("GET", path, self.request("GET", path).call())
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        response_body("POST", path, self.request("POST", path).send_form(form))
+    }
+}
+
+// Client wraps a transport with the requests we make of the site.
+pub struct Client {
+    transport: Box<dyn Transport>,
+}
+
+impl Client {
+    pub fn new(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Box::new(transport),
+        }
+    }
+
+    // input downloads a puzzle input
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        self.transport
+            .get(&format!("/{year}/day/{day}/input"))
+            .with_context(|| format!("failed to fetch input for {year} day {day}"))
+    }
+
+    // submit posts an answer for a part, 1 or 2, and returns the site's verdict
+    pub fn submit(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<Response> {
+        let body = self
+            .transport
+            .post(
+                &format!("/{year}/day/{day}/answer"),
+                &[("level", &part.to_string()), ("answer", answer)],
+            )
+            .with_context(|| format!("failed to submit part {part} of {year} day {day}"))?;
+
+        body.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_server::TestServer;
+
+    #[test]
+    fn test_http_get() {
+        let server = TestServer::start(vec![(200, "1\n2\n3\n")]);
+        let http = Http::with_base(&server.base, "abc123\n");
+
+        assert_eq!("1\n2\n3\n", http.get("/2023/day/1/input").unwrap());
+
+        let reqs = server.requests();
+        assert_eq!(1, reqs.len());
+        assert_eq!("GET", reqs[0].method);
+        assert_eq!("/2023/day/1/input", reqs[0].path);
+        assert_eq!(Some("session=abc123"), reqs[0].header("cookie"));
+        assert_eq!(Some(USER_AGENT), reqs[0].header("user-agent"));
+    }
+
+    #[test]
+    fn test_http_error_status() {
+        let server = TestServer::start(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let client = Client::new(Http::with_base(&server.base, "abc123"));
+
+        let err = client.input(2023, 25).unwrap_err();
+        assert!(format!("{err:#}").contains("404: Please don't repeatedly"));
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = TestServer::start(vec![(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        )]);
+        let client = Client::new(Http::with_base(&server.base, "abc123"));
+
+        let resp = client.submit(2023, 9, 2, "1097").unwrap();
+        assert_eq!(Verdict::Correct, resp.verdict);
+
+        let reqs = server.requests();
+        assert_eq!("POST", reqs[0].method);
+        assert_eq!("/2023/day/9/answer", reqs[0].path);
+        assert_eq!(Some("session=abc123"), reqs[0].header("cookie"));
+        assert_eq!("level=2&answer=1097", reqs[0].body);
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::html::{between, text};
+use crate::prelude::*;
+
+// Verdict is how the site judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // an answer was submitted too recently, so this one wasn't checked
+    Throttled,
+    // the part was already solved, so this one wasn't checked
+    AlreadySolved,
+}
+
+// Response is the site's reply to a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    // how long until another answer can be submitted, if we were told
+    pub wait: Option<Duration>,
+    // the message from the site with markup removed
+    pub message: String,
+}
+
+impl FromStr for Response {
+    type Err = Error;
+
+    // parses the html page returned after submitting an answer
+    fn from_str(s: &str) -> Result<Self> {
+        let message = article_text(s);
+
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("answer is too high") {
+                Verdict::TooHigh
+            } else if message.contains("answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::Throttled
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            bail!("unrecognized response to submission: {message}");
+        };
+
+        let lower = message.to_lowercase();
+        let wait = between(&lower, "you have ", " left to wait")
+            .or_else(|| between(&lower, "please wait ", " before trying again"))
+            .map(parse_wait)
+            .transpose()?;
+
+        Ok(Self {
+            verdict,
+            wait,
+            message,
+        })
+    }
+}
+
+// returns the text of the page's <article>, or the whole page if there's none,
+// with whitespace collapsed
+fn article_text(page: &str) -> String {
+    let article = between(page, "<article>", "</article>").unwrap_or(page);
+    text(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// parses the waits the site gives, e.g. "one minute", "5 minutes", or "1m 25s"
+fn parse_wait(s: &str) -> Result<Duration> {
+    let mut secs = 0;
+    let mut words = s.split_whitespace();
+
+    while let Some(w) = words.next() {
+        let (n, unit) = match w.find(|c: char| !c.is_ascii_digit()) {
+            // "1m", "25s"
+            Some(i) if i > 0 => (w[..i].parse::<u64>()?, &w[i..]),
+            // "one minute", "5 minutes"
+            _ => {
+                let n = match w {
+                    "one" | "a" | "an" => 1,
+                    w => w.parse().with_context(|| format!("invalid wait '{s}'"))?,
+                };
+                (n, words.next().unwrap_or_default())
+            }
+        };
+
+        secs += n * match unit.trim_end_matches('s') {
+            "h" | "hour" => 3600,
+            "m" | "minute" => 60,
+            "" | "second" => 1,
+            _ => bail!("invalid wait '{s}'"),
+        };
+    }
+
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_correct() {
+        let resp: Response = page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to restoring snow operations. <a href=\"/2023/day/9#part2\">[Continue to Part Two]</a>").parse().unwrap();
+
+        assert_eq!(Verdict::Correct, resp.verdict);
+        assert_eq!(None, resp.wait);
+        assert!(resp
+            .message
+            .starts_with("That's the right answer! You are one gold star closer"));
+    }
+
+    #[test]
+    fn test_wrong() {
+        let resp: Response = page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/9\">[Return to Day 9]</a>").parse().unwrap();
+        assert_eq!(Verdict::TooHigh, resp.verdict);
+        assert_eq!(Some(Duration::from_secs(60)), resp.wait);
+
+        let resp: Response = page("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.").parse().unwrap();
+        assert_eq!(Verdict::TooLow, resp.verdict);
+        assert_eq!(Some(Duration::from_secs(300)), resp.wait);
+
+        let resp: Response = page("That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again.").parse().unwrap();
+        assert_eq!(Verdict::Wrong, resp.verdict);
+    }
+
+    #[test]
+    fn test_throttled() {
+        let resp: Response = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 25s left to wait. <a href=\"/2023/day/9\">[Return to Day 9]</a>").parse().unwrap();
+        assert_eq!(Verdict::Throttled, resp.verdict);
+        assert_eq!(Some(Duration::from_secs(85)), resp.wait);
+
+        let resp: Response = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.").parse().unwrap();
+        assert_eq!(Some(Duration::from_secs(34)), resp.wait);
+    }
+
+    #[test]
+    fn test_already_solved() {
+        let resp: Response = page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/9\">[Return to Day 9]</a>").parse().unwrap();
+        assert_eq!(Verdict::AlreadySolved, resp.verdict);
+    }
+
+    #[test]
+    fn test_unrecognized() {
+        assert!(page("Puzzle inputs differ by user.")
+            .parse::<Response>()
+            .is_err());
+    }
+}
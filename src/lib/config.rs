@@ -0,0 +1,77 @@
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+// SESSION_ENV overrides the session token in the config file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+// Config is user configuration which doesn't belong in the repository, read
+// from $XDG_CONFIG_HOME/advent-of-code/config.toml by default:
+//
+//   session = "53616c7465645f5f..."
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // the session cookie from a logged in browser, used to fetch inputs
+    pub session: Option<String>,
+}
+
+impl FromStr for Config {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+}
+
+impl Config {
+    // default_path is where the config is read from if no path is provided
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+            .map(|dir| dir.join("advent-of-code").join("config.toml"))
+    }
+
+    // load reads the config at path, or the default path if there is none. A
+    // missing default config is treated as empty.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = match (path, Self::default_path()) {
+            (Some(path), _) => Self::read(path)?,
+            (None, Some(path)) if path.exists() => Self::read(&path)?,
+            (None, _) => Self::default(),
+        };
+
+        if let Ok(session) = env::var(SESSION_ENV) {
+            config.session = Some(session);
+        }
+
+        Ok(config)
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?
+            .parse()
+            .with_context(|| format!("failed to parse config {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Config::default(), "".parse().unwrap());
+        assert_eq!(
+            Some("abc123".to_string()),
+            "session = \"abc123\"".parse::<Config>().unwrap().session
+        );
+        assert!("sesion = \"abc123\"".parse::<Config>().is_err());
+    }
+}
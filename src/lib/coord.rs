@@ -1,14 +1,223 @@
 use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
+use num::NumCast;
+
 use super::prelude::*;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Coordinate<T> {
     pub x: T,
     pub y: T,
 }
 
+// Orientation is which way y grows. Inputs are read as lines of text, so grid::Grid
+// is YDown, but neighbor_map and days simulating physics are YUp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    // up is y + 1, like a graph
+    YUp,
+    // up is y - 1, like lines of text
+    YDown,
+}
+
+// Direction is a way to step from a tile to one of the eight around it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+use Direction::*;
+
+impl Direction {
+    // the four directions which aren't diagonal, clockwise from up
+    pub const CARDINAL: [Direction; 4] = [Up, Right, Down, Left];
+    // every direction, clockwise from up
+    pub const ALL: [Direction; 8] = [Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft];
+
+    pub fn is_diagonal(self) -> bool {
+        matches!(self, UpRight | DownRight | DownLeft | UpLeft)
+    }
+
+    // rotate turns clockwise by eighths of a turn, or counterclockwise if negative
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    // delta is what's added to a coordinate to step once in this direction
+    pub fn delta(self, orientation: Orientation) -> Coordinate<i64> {
+        let (x, up) = match self {
+            Up => (0, 1),
+            UpRight => (1, 1),
+            Right => (1, 0),
+            DownRight => (1, -1),
+            Down => (0, -1),
+            DownLeft => (-1, -1),
+            Left => (-1, 0),
+            UpLeft => (-1, 1),
+        };
+
+        match orientation {
+            Orientation::YUp => Coordinate { x, y: up },
+            Orientation::YDown => Coordinate { x, y: -up },
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    // parses the cardinal directions as letters, compass points or arrows
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "U" | "N" | "^" => Up,
+            "R" | "E" | ">" => Right,
+            "D" | "S" | "v" => Down,
+            "L" | "W" | "<" => Left,
+            _ => bail!("unknown direction '{s}'"),
+        })
+    }
+}
+
+impl<T> Coordinate<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Coordinate<T> {
+    // manhattan is the distance between coordinates moving only along x or y
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    // chebyshev is the distance between coordinates moving diagonally too
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+// works for unsigned types, unlike (a - b).abs()
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: NumCast + Copy> Coordinate<T> {
+    // cast converts to a coordinate of another number type, or None if x or y
+    // doesn't fit, e.g. a negative x to usize
+    pub fn cast<U: NumCast>(self) -> Option<Coordinate<U>> {
+        Some(Coordinate {
+            x: U::from(self.x)?,
+            y: U::from(self.y)?,
+        })
+    }
+
+    // step returns the coordinate one step in direction, or None if it doesn't fit
+    // in T, e.g. left of x = 0 in usize
+    pub fn step(self, direction: Direction, orientation: Orientation) -> Option<Self> {
+        (self.cast::<i64>()? + direction.delta(orientation)).cast()
+    }
+}
+
+impl<T> From<(T, T)> for Coordinate<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Coordinate<T>> for (T, T) {
+    fn from(c: Coordinate<T>) -> Self {
+        (c.x, c.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Coordinate<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Coordinate<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Coordinate<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Coordinate<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+// scales both x and y
+impl<T: Mul<Output = T> + Copy> Mul<T> for Coordinate<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Coordinate<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl<T: FromStr + fmt::Display> FromStr for Coordinate<T>
 where
     <T as FromStr>::Err: fmt::Display,
@@ -38,3 +247,85 @@ impl<T: fmt::Display> fmt::Display for Coordinate<T> {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Coordinate::new(3, -2);
+        let b = Coordinate::new(-1, 5);
+
+        assert_eq!(Coordinate::new(2, 3), a + b);
+        assert_eq!(Coordinate::new(4, -7), a - b);
+        assert_eq!(Coordinate::new(9, -6), a * 3);
+        assert_eq!(Coordinate::new(-3, 2), -a);
+
+        let mut c = a;
+        c += b;
+        c -= Coordinate::new(1, 1);
+        assert_eq!(Coordinate::new(1, 2), c);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Coordinate::new(3, -2);
+        let b = Coordinate::new(-1, 5);
+        assert_eq!(11, a.manhattan(&b));
+        assert_eq!(7, a.chebyshev(&b));
+
+        // unsigned coordinates don't underflow
+        let a = Coordinate::<usize>::new(1, 6);
+        let b = Coordinate::<usize>::new(4, 2);
+        assert_eq!(7, a.manhattan(&b));
+        assert_eq!(7, b.manhattan(&a));
+        assert_eq!(4, a.chebyshev(&b));
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Right, Up.turn_right());
+        assert_eq!(Left, Up.turn_left());
+        assert_eq!(DownLeft, UpRight.opposite());
+        assert_eq!(UpLeft, Up.rotate(-1));
+        assert_eq!(Up, UpLeft.rotate(9));
+        assert!(DownRight.is_diagonal());
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+
+        for d in Direction::ALL {
+            assert_eq!(
+                -d.delta(Orientation::YUp),
+                d.opposite().delta(Orientation::YUp)
+            );
+            assert_eq!(d.turn_right(), d.turn_left().opposite());
+        }
+
+        assert_eq!(Coordinate::new(0, 1), Up.delta(Orientation::YUp));
+        assert_eq!(Coordinate::new(0, -1), Up.delta(Orientation::YDown));
+        assert_eq!(Coordinate::new(1, 1), DownRight.delta(Orientation::YDown));
+        assert_eq!(Left, "<".parse().unwrap());
+        assert_eq!(Down, "D".parse().unwrap());
+        assert!("X".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let c = Coordinate::<usize>::new(0, 2);
+        assert_eq!(Some(Coordinate::new(0i64, 2)), c.cast());
+        assert_eq!(None, Coordinate::new(-1i64, 2).cast::<usize>());
+        assert_eq!(None, Coordinate::new(300i64, 2).cast::<u8>());
+
+        assert_eq!(None, c.step(Left, Orientation::YUp));
+        assert_eq!(
+            Some(Coordinate::new(1, 1)),
+            c.step(UpRight, Orientation::YDown)
+        );
+        assert_eq!(
+            Some(Coordinate::new(1, 3)),
+            c.step(UpRight, Orientation::YUp)
+        );
+        assert_eq!((0, 2), c.into());
+        assert_eq!(c, (0, 2).into());
+    }
+}
@@ -1,10 +1,15 @@
-#![allow(dead_code)]
+use std::collections::HashSet;
 use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
+use num::{PrimInt, Signed};
+
 use super::prelude::*;
+use crate::search;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Coordinate<T> {
     pub x: T,
     pub y: T,
@@ -17,10 +22,9 @@ where
 {
     type Err = Error;
 
-    // takes line-by-line representation of wall lines
-    // and derives a Map
+    // parses "x,y,z"
     fn from_str(s: &str) -> Result<Self> {
-        let mut parts = s.splitn(3, ",").map(|p| p.trim().parse());
+        let mut parts = s.splitn(3, ',').map(|p| p.trim().parse());
 
         Ok(Self {
             x: parts
@@ -46,31 +50,41 @@ impl<T: fmt::Display> fmt::Display for Coordinate<T> {
 }
 
 impl<T> Coordinate<T> {
-    fn new(x: T, y: T, z: T) -> Self {
-        Self {
-            x,
-            y,
-            z,
-        }
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
     }
 }
 
-impl<T: Copy + std::ops::Add<i32, Output = T> + std::ops::Sub<i32, Output = T>> Coordinate<T> {
-    fn directions(&self) -> impl Iterator<Item=Coordinate<T>> {
-        let (x, y, z) = (self.x, self.y, self.z);
-        let v = vec![
-            (x - 1, y, z),
-            (x, y - 1, z),
-            (x, y, z - 1),
-            (x + 1, y , z),
-            (x, y + 1, z),
-            (x, y, z + 1),
-        ].into_iter()
-            .map(|c| c.into())
-            .collect::<Vec<Coordinate<T>>>();
+impl<T: PrimInt + Signed> Coordinate<T> {
+    // neighbors returns the six coordinates sharing a face with this one
+    pub fn neighbors(&self) -> impl Iterator<Item = Coordinate<T>> {
+        let c = *self;
+        offsets()
+            .filter(|d: &Coordinate<T>| d.x.abs() + d.y.abs() + d.z.abs() == T::one())
+            .map(move |d| c + d)
+    }
 
-        v.into_iter()
+    // neighbors_diagonal returns the 26 coordinates sharing a face, edge or corner
+    // with this one
+    pub fn neighbors_diagonal(&self) -> impl Iterator<Item = Coordinate<T>> {
+        let c = *self;
+        offsets().map(move |d| c + d)
     }
+
+    // manhattan is the distance between coordinates moving only along an axis
+    pub fn manhattan(&self, other: &Self) -> T {
+        let d = *self - *other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+}
+
+// offsets returns every coordinate with -1, 0 or 1 on each axis, except the origin
+fn offsets<T: PrimInt + Signed>() -> impl Iterator<Item = Coordinate<T>> {
+    let unit = [-T::one(), T::zero(), T::one()];
+    unit.into_iter()
+        .flat_map(move |x| unit.into_iter().map(move |y| (x, y)))
+        .flat_map(move |(x, y)| unit.into_iter().map(move |z| Coordinate::new(x, y, z)))
+        .filter(|d| *d != Coordinate::new(T::zero(), T::zero(), T::zero()))
 }
 
 impl<T> From<(T, T, T)> for Coordinate<T> {
@@ -78,3 +92,354 @@ impl<T> From<(T, T, T)> for Coordinate<T> {
         Coordinate::new(x, y, z)
     }
 }
+
+impl<T: Add<Output = T>> Add for Coordinate<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Coordinate<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Coordinate<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Coordinate<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+// scales every axis
+impl<T: Mul<Output = T> + Copy> Mul<T> for Coordinate<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Coordinate<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// Aabb is an axis aligned bounding box, including both its min and max corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb<T> {
+    pub min: Coordinate<T>,
+    pub max: Coordinate<T>,
+}
+
+impl<T: PrimInt> Aabb<T> {
+    // new returns the box with corners a and b, in any order
+    pub fn new(a: Coordinate<T>, b: Coordinate<T>) -> Self {
+        Self {
+            min: Coordinate::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Coordinate::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    // around returns the smallest box containing every point, or None without any
+    pub fn around(points: impl IntoIterator<Item = Coordinate<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut b = Self::new(first, first);
+        for p in points {
+            b.extend(p);
+        }
+
+        Some(b)
+    }
+
+    // extend grows the box to contain p
+    pub fn extend(&mut self, p: Coordinate<T>) {
+        *self = Self::new(
+            Coordinate::new(
+                self.min.x.min(p.x),
+                self.min.y.min(p.y),
+                self.min.z.min(p.z),
+            ),
+            Coordinate::new(
+                self.max.x.max(p.x),
+                self.max.y.max(p.y),
+                self.max.z.max(p.z),
+            ),
+        );
+    }
+
+    // grow returns the box with by added to every side
+    pub fn grow(&self, by: T) -> Self {
+        let by = Coordinate::new(by, by, by);
+        Self::new(self.min - by, self.max + by)
+    }
+
+    pub fn contains(&self, p: &Coordinate<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    // intersection returns the box both boxes contain, if they overlap
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Coordinate::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Coordinate::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // size is the number of coordinates along each axis
+    pub fn size(&self) -> Coordinate<T> {
+        self.max - self.min + Coordinate::new(T::one(), T::one(), T::one())
+    }
+
+    pub fn volume(&self) -> T {
+        let s = self.size();
+        s.x * s.y * s.z
+    }
+
+    // iter walks every coordinate in the box, by x, then y, then z
+    pub fn iter(&self) -> impl Iterator<Item = Coordinate<T>> {
+        let Self { min, max } = *self;
+        num::range_inclusive(min.x, max.x).flat_map(move |x| {
+            num::range_inclusive(min.y, max.y).flat_map(move |y| {
+                num::range_inclusive(min.z, max.z).map(move |z| Coordinate::new(x, y, z))
+            })
+        })
+    }
+}
+
+// Voxels is a sparse set of filled unit cubes, each named by its coordinate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct Voxels<T: Hash + Eq>(HashSet<Coordinate<T>>);
+
+impl<T: PrimInt + Signed + Hash> Voxels<T> {
+    pub fn new() -> Self {
+        Self(HashSet::new())
+    }
+
+    // bounds returns the smallest box containing every voxel
+    pub fn bounds(&self) -> Option<Aabb<T>> {
+        Aabb::around(self.0.iter().copied())
+    }
+
+    // surface_area counts the faces of voxels which aren't against another voxel,
+    // including those facing pockets of air trapped inside
+    pub fn surface_area(&self) -> usize {
+        self.0
+            .iter()
+            .flat_map(|v| v.neighbors())
+            .filter(|n| !self.contains(n))
+            .count()
+    }
+
+    // exterior_surface_area is surface_area only counting faces which can be
+    // reached from outside
+    pub fn exterior_surface_area(&self) -> usize {
+        let outside = self.exterior();
+        self.0
+            .iter()
+            .flat_map(|v| v.neighbors())
+            .filter(|n| outside.contains(n))
+            .count()
+    }
+
+    // exterior returns the empty coordinates which can be reached from outside,
+    // out to a layer of air around the bounds
+    pub fn exterior(&self) -> HashSet<Coordinate<T>> {
+        let Some(bounds) = self.bounds() else {
+            return HashSet::new();
+        };
+
+        let within = bounds.grow(T::one());
+        self.flood_fill(within.min, &within)
+    }
+
+    // flood_fill returns every empty coordinate within bounds which can be
+    // reached from start through faces, including start. Nothing is reached if
+    // start is filled or out of bounds.
+    pub fn flood_fill(&self, start: Coordinate<T>, bounds: &Aabb<T>) -> HashSet<Coordinate<T>> {
+        let open = |c: &Coordinate<T>| bounds.contains(c) && !self.contains(c);
+        if !open(&start) {
+            return HashSet::new();
+        }
+
+        let g = search::from_fn(|c: &Coordinate<T>| {
+            c.neighbors().filter(open).map(|n| (n, 1)).collect()
+        });
+        search::bfs_all(&g, [start])
+            .iter()
+            .map(|(c, _)| *c)
+            .collect()
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<Coordinate<T>> for Voxels<T> {
+    fn from_iter<I: IntoIterator<Item = Coordinate<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T: Hash + Eq + FromStr + fmt::Display> FromStr for Voxels<T>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
+    type Err = Error;
+
+    // parses a voxel per line as "x,y,z"
+    fn from_str(s: &str) -> Result<Self> {
+        s.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(FromStr::from_str)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2022 day 18's lava droplet
+    const DROPLET: &str = r"2,2,2
+        1,2,2
+        3,2,2
+        2,1,2
+        2,3,2
+        2,2,1
+        2,2,3
+        2,2,4
+        2,2,6
+        1,2,5
+        3,2,5
+        2,1,5
+        2,3,5";
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Coordinate::new(1, -2, 3);
+        let b = Coordinate::new(4, 5, -6);
+
+        assert_eq!(Coordinate::new(5, 3, -3), a + b);
+        assert_eq!(Coordinate::new(-3, -7, 9), a - b);
+        assert_eq!(Coordinate::new(2, -4, 6), a * 2);
+        assert_eq!(Coordinate::new(-1, 2, -3), -a);
+        assert_eq!(19, a.manhattan(&b));
+
+        let mut c = a;
+        c += b;
+        c -= Coordinate::new(1, 1, 1);
+        assert_eq!(Coordinate::new(4, 2, -4), c);
+        assert_eq!(
+            Ok(c),
+            "4, 2,-4"
+                .parse::<Coordinate<i32>>()
+                .map_err(|e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let c = Coordinate::new(0i64, 0, 0);
+
+        let faces = c.neighbors().collect::<HashSet<_>>();
+        assert_eq!(6, faces.len());
+        assert!(faces.iter().all(|n| n.manhattan(&c) == 1));
+
+        let all = c.neighbors_diagonal().collect::<HashSet<_>>();
+        assert_eq!(26, all.len());
+        assert!(!all.contains(&c));
+        assert!(faces.is_subset(&all));
+    }
+
+    #[test]
+    fn test_aabb() {
+        let b = Aabb::new(Coordinate::new(2, 0, 5), Coordinate::new(0, 3, 4));
+        assert_eq!(Coordinate::new(0, 0, 4), b.min);
+        assert_eq!(Coordinate::new(3, 4, 2), b.size());
+        assert_eq!(24, b.volume());
+        assert_eq!(24, b.iter().count());
+        assert!(b.iter().all(|c| b.contains(&c)));
+        assert!(!b.contains(&Coordinate::new(3, 0, 4)));
+
+        let other = Aabb::new(Coordinate::new(1, 1, 5), Coordinate::new(9, 9, 9));
+        assert_eq!(
+            Some(Aabb::new(
+                Coordinate::new(1, 1, 5),
+                Coordinate::new(2, 3, 5)
+            )),
+            b.intersection(&other)
+        );
+        assert!(!b.intersects(&Aabb::new(
+            Coordinate::new(3, 0, 0),
+            Coordinate::new(4, 4, 4)
+        )));
+
+        assert_eq!(
+            Aabb::new(Coordinate::new(-1, -1, 3), Coordinate::new(3, 4, 6)),
+            b.grow(1)
+        );
+        assert_eq!(
+            Some(b),
+            Aabb::around([b.min, Coordinate::new(1, 1, 4), b.max])
+        );
+        assert_eq!(None, Aabb::<i32>::around([]));
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let v: Voxels<i32> = "1,1,1\n2,1,1".parse().unwrap();
+        assert_eq!(10, v.surface_area());
+
+        let v: Voxels<i32> = DROPLET.parse().unwrap();
+        assert_eq!(64, v.surface_area());
+        assert_eq!(58, v.exterior_surface_area());
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        // a hollow 3x3x3 cube with one air pocket in the middle
+        let shell = Aabb::new(Coordinate::new(0, 0, 0), Coordinate::new(2, 2, 2));
+        let middle = Coordinate::new(1, 1, 1);
+        let v = shell
+            .iter()
+            .filter(|c| *c != middle)
+            .collect::<Voxels<i64>>();
+
+        assert_eq!(HashSet::from([middle]), v.flood_fill(middle, &shell));
+        assert!(v.flood_fill(Coordinate::new(0, 0, 0), &shell).is_empty());
+        assert!(!v.exterior().contains(&middle));
+        assert_eq!(6 * 9 + 6, v.surface_area());
+        assert_eq!(6 * 9, v.exterior_surface_area());
+    }
+}
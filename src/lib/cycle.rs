@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::prelude::*;
+
+// Cycle is where an eventually repeating sequence x0, f(x0), f(f(x0)), ...
+// starts repeating: the value at step start is the first one seen again, and it's
+// seen again every len steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    // equivalent returns the first step with the same value as step n
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        self.start + (n - self.start) % self.len
+    }
+}
+
+// floyd finds the cycle in the sequence from x0 by stepping a tortoise one step at
+// a time and a hare two. It only keeps two values around, but calls f about three
+// times as often as brent. The sequence must repeat eventually or this never
+// returns.
+pub fn floyd<T: Clone + PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // the hare is twice as far as the tortoise when they meet, so the tortoise
+    // is a multiple of the cycle's length from x0
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    // restarting the tortoise, they meet again where the cycle starts
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+
+    Cycle { start, len }
+}
+
+// brent finds the cycle in the sequence from x0 by teleporting the tortoise to the
+// hare at powers of two, which finds the cycle's length directly. Like floyd, the
+// sequence must repeat eventually.
+pub fn brent<T: Clone + PartialEq>(x0: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    // with the hare len steps ahead, they meet where the cycle starts
+    let mut start = 0;
+    tortoise = x0.clone();
+    hare = x0;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+// extrapolate returns value(state) after step has been applied n times. Rather
+// than stepping n times, it steps until key(state) repeats and assumes everything
+// after repeats too, with value changing by the same amount each cycle. The key
+// must capture everything which decides how later steps change the state,
+// but can leave out things like the value, e.g. a tower's height. state is left
+// where the cycle was found, not where it would be after n steps.
+pub fn extrapolate<S, K: Hash + Eq>(
+    state: &mut S,
+    n: usize,
+    mut step: impl FnMut(&mut S) -> Result<()>,
+    mut key: impl FnMut(&S) -> K,
+    mut value: impl FnMut(&S) -> i64,
+) -> Result<i64> {
+    let mut seen = HashMap::new();
+    // values[i] is the value after i steps
+    let mut values = vec![];
+
+    for i in 0.. {
+        values.push(value(state));
+        if i == n {
+            break;
+        }
+
+        if let Some(start) = seen.insert(key(state), i) {
+            let cycle = Cycle {
+                start,
+                len: i - start,
+            };
+            let per_cycle = values[i] - values[start];
+            let cycles = ((n - start) / cycle.len) as i64;
+            debug!(
+                "steps {start} and {i} reach the same state, skipping {cycles} cycles changing the value by {per_cycle} each"
+            );
+
+            return Ok(values[cycle.equivalent(n)] + cycles * per_cycle);
+        }
+
+        step(state)?;
+    }
+
+    Ok(values[n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the sequence from x0 under x -> (x^2 + c) mod m, stepped by hand
+    fn naive(x0: u64, c: u64, m: u64) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut x = x0;
+        for i in 0.. {
+            if let Some(start) = seen.insert(x, i) {
+                return Cycle {
+                    start,
+                    len: i - start,
+                };
+            }
+            x = (x * x + c) % m;
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_floyd_and_brent() {
+        for (x0, c, m) in [(0, 1, 255), (3, 7, 1000), (2, 1, 10007), (5, 0, 7)] {
+            let f = |x: &u64| (x * x + c) % m;
+            let expected = naive(x0, c, m);
+            assert_eq!(expected, floyd(x0, f), "floyd {x0} {c} {m}");
+            assert_eq!(expected, brent(x0, f), "brent {x0} {c} {m}");
+        }
+
+        // already cycling, and a fixed point
+        assert_eq!(Cycle { start: 0, len: 3 }, brent(0, |x| (x + 1) % 3));
+        assert_eq!(Cycle { start: 0, len: 1 }, floyd(4, |x| *x));
+    }
+
+    #[test]
+    fn test_equivalent() {
+        let c = Cycle { start: 2, len: 3 };
+        assert_eq!(
+            vec![0, 1, 2, 3, 4, 2, 3, 4, 2],
+            (0..9).map(|n| c.equivalent(n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // position runs up from 0 to 3 then cycles through 3..=7, and total
+        // grows by the position each step
+        let step = |(pos, total): &mut (u64, i64)| {
+            *pos = if *pos < 3 {
+                *pos + 1
+            } else {
+                3 + (*pos - 2) % 5
+            };
+            *total += *pos as i64;
+            Ok(())
+        };
+
+        for n in [0, 2, 3, 10, 57, 1000] {
+            let mut expected = (0, 0);
+            for _ in 0..n {
+                step(&mut expected).unwrap();
+            }
+
+            let mut state = (0, 0);
+            let total = extrapolate(&mut state, n, step, |(pos, _)| *pos, |(_, t)| *t);
+            assert_eq!(expected.1, total.unwrap(), "{n} steps");
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+use crate::solver::{self, Puzzle};
+use crate::verify::expected_answer;
+
+// DEFAULT_DIR is where example fixtures are stored, relative to the repository root.
+pub const DEFAULT_DIR: &str = "fixtures";
+
+// dir returns where the fixtures for a year and day live: <dir>/<year>/<day>/
+pub fn dir(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string()).join(day.to_string())
+}
+
+// Fixture is an example input with the answers expected for it. Each is a TOML
+// file under fixtures/<year>/<day>/, and build.rs generates a test for each one:
+//
+//   part1 = 114
+//   part2 = 2
+//   input = '''
+//   0 3 6 9 12 15
+//   1 3 6 10 15 21
+//   '''
+//
+// Either part may be left out, e.g. when an example only applies to one part.
+// The input is used as is, so it ends with a newline like a real input would.
+// A fixture the solver doesn't pass yet can set `ignore = "<reason>"`, which
+// ignores its test. Days which also have an elisp solution can record what it
+// printed for part 1 as `elisp_part1`, which part 1 is checked against too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub input: String,
+    pub answers: [Option<Answer>; 2],
+    pub elisp_part1: Option<Answer>,
+    pub ignore: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawFixture {
+    input: String,
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+    elisp_part1: Option<toml::Value>,
+    ignore: Option<String>,
+}
+
+impl FromStr for Fixture {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let raw: RawFixture = toml::from_str(s)?;
+        let answers = [
+            raw.part1.map(expected_answer).transpose()?,
+            raw.part2.map(expected_answer).transpose()?,
+        ];
+        ensure!(
+            answers.iter().any(Option::is_some),
+            "fixture has no expected answers"
+        );
+
+        Ok(Self {
+            input: raw.input,
+            answers,
+            elisp_part1: raw.elisp_part1.map(expected_answer).transpose()?,
+            ignore: raw.ignore,
+        })
+    }
+}
+
+impl Fixture {
+    pub fn load(path: &Path) -> Result<Self> {
+        read_to_string(path)
+            .with_context(|| format!("failed to read fixture {}", path.display()))?
+            .parse()
+            .with_context(|| format!("failed to parse fixture {}", path.display()))
+    }
+
+    // to_toml formats the fixture the way fixtures are written by hand, with the
+    // input as a literal multi-line string where possible
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        if let Some(reason) = &self.ignore {
+            out += &format!("ignore = {}\n", toml::Value::from(reason.as_str()));
+        }
+        let value = |a: &Answer| match a {
+            Answer::Int(i) if i64::try_from(*i).is_ok() => i.to_string(),
+            a if a.is_multiline() => format!("'''\n{a}\n'''"),
+            a => toml::Value::from(a.to_string()).to_string(),
+        };
+        for (part, answer) in (1..=2).zip(&self.answers) {
+            if let Some(a) = answer {
+                out += &format!("part{part} = {}\n", value(a));
+            }
+        }
+        if let Some(a) = &self.elisp_part1 {
+            out += &format!("elisp_part1 = {}\n", value(a));
+        }
+
+        if self.input.contains("'''") {
+            out += &format!("input = {}\n", toml::Value::from(self.input.as_str()));
+        } else {
+            out += &format!("input = '''\n{}'''\n", self.input);
+        }
+
+        out
+    }
+
+    // check solves the parts which have expected answers and returns an error
+    // describing every part that doesn't match
+    pub fn check(&self, puzzle: &Puzzle) -> Result<()> {
+        let parsed = puzzle.parse(&self.input)?;
+
+        let mut failures = vec![];
+        for (part, expected) in (1..=2).zip(&self.answers) {
+            let Some(expected) = expected else {
+                continue;
+            };
+
+            let actual = match part {
+                1 => puzzle.part1(&parsed)?,
+                _ => puzzle.part2(&parsed)?,
+            };
+            if part == 1 {
+                if let Some(elisp) = self.elisp_part1.as_ref().filter(|e| **e != actual) {
+                    failures.push(format!("part 1: elisp solution gave {elisp}, got {actual}"));
+                }
+            }
+            if actual == *expected {
+                continue;
+            }
+
+            if expected.is_multiline() || actual.is_multiline() {
+                failures.push(format!(
+                    "part {part}: expected:\n{expected}\ngot:\n{actual}"
+                ));
+            } else {
+                failures.push(format!("part {part}: expected {expected}, got {actual}"));
+            }
+        }
+
+        ensure!(failures.is_empty(), "{}", failures.join("\n"));
+        Ok(())
+    }
+}
+
+// check_file checks the fixture at path against the puzzle registered for its
+// year and day. It's called by the tests generated by build.rs.
+pub fn check_file(year: u32, day: u32, path: &Path) -> Result<()> {
+    let puzzle = solver::get(year, day).ok_or_else(|| {
+        anyhow!(
+            "{} is for {year} day {day}, which has no solver",
+            path.display()
+        )
+    })?;
+
+    Fixture::load(path)?
+        .check(puzzle)
+        .with_context(|| format!("{} failed", path.display()))
+}
+
+#[cfg(test)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process::Command;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let f: Fixture = "part2 = 19\ninput = '''\nmjqjpqmgbljsphdztnvjfqwrcgsmlb\n'''\n"
+            .parse()
+            .unwrap();
+        assert_eq!("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n", f.input);
+        assert_eq!([None, Some(Answer::Int(19))], f.answers);
+
+        assert!("input = 'abc'".parse::<Fixture>().is_err());
+        assert!("part1 = 1".parse::<Fixture>().is_err());
+        assert!("part3 = 1\ninput = 'abc'".parse::<Fixture>().is_err());
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let f = Fixture {
+            input: "1\n2\n".to_string(),
+            answers: [Some(Answer::Int(3)), Some("ab".into())],
+            elisp_part1: None,
+            ignore: None,
+        };
+        assert_eq!(
+            "part1 = 3\npart2 = \"ab\"\ninput = '''\n1\n2\n'''\n",
+            f.to_toml()
+        );
+        assert_eq!(f, f.to_toml().parse().unwrap());
+
+        let f = Fixture {
+            input: "''' \"\n".to_string(),
+            answers: [None, Some(Answer::Lines(vec!["#.".into(), ".#".into()]))],
+            elisp_part1: None,
+            ignore: Some("slow".to_string()),
+        };
+        assert_eq!(f, f.to_toml().parse().unwrap());
+
+        let f = Fixture {
+            input: "1\n".to_string(),
+            answers: [Some(Answer::Int(1)), None],
+            elisp_part1: Some(Answer::Int(1)),
+            ignore: None,
+        };
+        assert_eq!(
+            "part1 = 1\nelisp_part1 = 1\ninput = '''\n1\n'''\n",
+            f.to_toml()
+        );
+        assert_eq!(f, f.to_toml().parse().unwrap());
+    }
+
+    #[test]
+    fn test_check() {
+        let puzzle = solver::get(2022, 6).unwrap();
+        let f: Fixture = "part1 = 7\npart2 = 20\ninput = 'mjqjpqmgbljsphdztnvjfqwrcgsmlb'"
+            .parse()
+            .unwrap();
+
+        let err = format!("{:#}", f.check(puzzle).unwrap_err());
+        assert_eq!("part 2: expected 20, got 19", err);
+
+        let f: Fixture = "part1 = 7\nelisp_part1 = 8\ninput = 'mjqjpqmgbljsphdztnvjfqwrcgsmlb'"
+            .parse()
+            .unwrap();
+        let err = format!("{:#}", f.check(puzzle).unwrap_err());
+        assert_eq!("part 1: elisp solution gave 8, got 7", err);
+    }
+
+    // elisp_part1 runs a day's main.el on input with emacs and returns what its
+    // last form evaluates to. main.el reads ./input.txt and loads
+    // ../../src/lib/common.el, so it's run from a directory two below the root.
+    fn elisp_part1(main: &Path, input: &str) -> Result<Answer> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let day = main.parent().and_then(Path::file_name).unwrap();
+        let dir = root
+            .join("target")
+            .join(format!("elisp-{}", day.to_string_lossy()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("input.txt"), input)?;
+
+        let eval = format!(
+            "(with-temp-buffer (insert-file-contents {:?}) \
+               (let (v) (condition-case nil \
+                 (while t (setq v (eval (read (current-buffer)) t))) \
+                 (end-of-file (princ v)))))",
+            main.display().to_string()
+        );
+        let out = Command::new("emacs")
+            .args(["-Q", "--batch", "--eval", &eval])
+            .current_dir(&dir)
+            .output()
+            .context("failed to run emacs")?;
+        ensure!(
+            out.status.success(),
+            "emacs failed: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+
+        String::from_utf8(out.stdout)?.trim().parse()
+    }
+
+    // checks the elisp answers recorded in the 2022 day 1-4 fixtures against
+    // what the elisp solutions actually print
+    #[test]
+    #[ignore = "needs emacs"]
+    fn test_elisp_part1() -> Result<()> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for day in 1..=4 {
+            let main = root.join(format!("src/lib/year_2022/day_{day:02}/main.el"));
+            for entry in fs::read_dir(dir(&root.join(DEFAULT_DIR), 2022, day))? {
+                let path = entry?.path();
+                let f = Fixture::load(&path)?;
+                let Some(expected) = f.elisp_part1 else {
+                    continue;
+                };
+
+                assert_eq!(
+                    expected,
+                    elisp_part1(&main, &f.input)?,
+                    "{}",
+                    path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::coord::{Coordinate, Direction, Orientation};
+use crate::prelude::*;
+
+// Grid is a dense, rectangular grid of tiles. Coordinates are (x, y) with (0, 0)
+// at the top left, so y counts rows down from the first line of an input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    // row by row: (x, y) is at y * width + x
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum GridError {
+    #[error("({x}, {y}) is out of bounds in a {width}x{height} grid")]
+    OutOfBounds {
+        x: i64,
+        y: i64,
+        width: usize,
+        height: usize,
+    },
+}
+
+impl<T: Default + Clone> Grid<T> {
+    // new returns a grid with every tile set to the default
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, T::default())
+    }
+
+    // resize changes the grid's dimensions, keeping the tiles still inside them
+    // and filling new ones with the default
+    pub fn resize(&mut self, width: usize, height: usize) {
+        if width == self.width {
+            self.data.resize(width * height, T::default());
+            self.height = height;
+            return;
+        }
+
+        let mut new = Self::new(width, height);
+        for y in 0..height.min(self.height) {
+            for x in 0..width.min(self.width) {
+                new[(x, y)] = self[(x, y)].clone();
+            }
+        }
+        *self = new;
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, tile: T) -> Self {
+        Self {
+            data: vec![tile; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub const ORIENTATION: Orientation = Orientation::YDown;
+
+    // from_rows builds a grid from rows of tiles, which must all be as wide
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            bail!(
+                "row {y} is {} wide but the first row is {width} wide",
+                row.len()
+            );
+        }
+
+        Ok(Self {
+            data: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    // parse reads a grid with a tile for each character, e.g. a puzzle input's
+    // map, converting each with tile. Blank lines around the grid are skipped, but
+    // spaces are passed to tile like any other character.
+    pub fn parse(s: &str, mut tile: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let rows = s
+            .trim_matches('\n')
+            .lines()
+            .enumerate()
+            .map(|(y, l)| {
+                l.strip_suffix('\r')
+                    .unwrap_or(l)
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| tile(c).with_context(|| format!("invalid tile at ({x}, {y})")))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // dimensions returns (width, height)
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.offset(x, y).is_some()
+    }
+
+    fn offset(&self, x: i64, y: i64) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn out_of_bounds(&self, x: i64, y: i64) -> GridError {
+        GridError::OutOfBounds {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Result<&T, GridError> {
+        self.get_signed(x as i64, y as i64)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut T, GridError> {
+        self.get_signed_mut(x as i64, y as i64)
+    }
+
+    // get_signed is get for coordinates which may have stepped off the grid,
+    // e.g. a neighbor of an edge tile
+    pub fn get_signed(&self, x: i64, y: i64) -> Result<&T, GridError> {
+        match self.offset(x, y) {
+            Some(i) => Ok(&self.data[i]),
+            None => Err(self.out_of_bounds(x, y)),
+        }
+    }
+
+    pub fn get_signed_mut(&mut self, x: i64, y: i64) -> Result<&mut T, GridError> {
+        match self.offset(x, y) {
+            Some(i) => Ok(&mut self.data[i]),
+            None => Err(self.out_of_bounds(x, y)),
+        }
+    }
+
+    // iter walks every tile with its coordinates, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, t)| ((i % width, i / width), t))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, t)| ((i % width, i / width), t))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and an empty grid has no rows anyway
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // column walks the tiles in column x from top to bottom. It's empty if x is
+    // out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let rows = if x < self.width { self.height } else { 0 };
+        self.data
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(rows)
+    }
+
+    // neighbors returns the tiles up, right, down and left of (x, y) which are
+    // in the grid
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.offsets(x, y, &Direction::CARDINAL)
+    }
+
+    // neighbors_diagonal is neighbors including the four diagonals, clockwise from up
+    pub fn neighbors_diagonal(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.offsets(x, y, &Direction::ALL)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        directions.iter().filter_map(move |d| {
+            let Coordinate { x, y } = Coordinate::new(x, y).step(*d, Self::ORIENTATION)?;
+            Some(((x, y), self.get(x, y).ok()?))
+        })
+    }
+
+    // positions returns the coordinates of every tile matching f, row by row
+    pub fn positions<'a>(
+        &'a self,
+        mut f: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter().filter(move |(_, t)| f(t)).map(|(c, _)| c)
+    }
+
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.positions(f).next()
+    }
+
+    // map returns a grid of the same size with f applied to every tile
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.get(x, y) {
+            Ok(t) => t,
+            Err(e) => panic!("{e}"),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = self.dimensions();
+        match self.get_mut(x, y) {
+            Ok(t) => t,
+            Err(_) => panic!("({x}, {y}) is out of bounds in a {width}x{height} grid"),
+        }
+    }
+}
+
+// Grid is drawn row by row, the way it's parsed
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows().take(self.height) {
+            for t in row {
+                write!(f, "{t}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#..\n.#.\n..#\n#.#\n";
+
+    fn parse(s: &str) -> Result<Grid<char>> {
+        Grid::parse(s, |c| match c {
+            '#' | '.' => Ok(c),
+            _ => bail!("unknown tile '{c}'"),
+        })
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = parse(EXAMPLE).unwrap();
+        assert_eq!((3, 4), g.dimensions());
+        assert_eq!('#', g[(0, 0)]);
+        assert_eq!('.', g[(1, 0)]);
+        assert_eq!('#', g[(2, 3)]);
+        assert_eq!(EXAMPLE, g.to_string());
+
+        let err = parse("..\n.x\n").unwrap_err();
+        assert_eq!(
+            "invalid tile at (1, 1): unknown tile 'x'",
+            format!("{err:#}")
+        );
+        assert!(parse("..\n.\n").is_err());
+
+        // spaces are tiles too, even at the end of a row
+        let g = Grid::parse("# \n #\r", Ok).unwrap();
+        assert_eq!((2, 2), g.dimensions());
+        assert_eq!(' ', g[(1, 0)]);
+        assert!(parse("#.\n# \n").is_err());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut g = parse(EXAMPLE).unwrap();
+        assert!(g.get(2, 3).is_ok());
+        assert_eq!(
+            Err(GridError::OutOfBounds {
+                x: 3,
+                y: 0,
+                width: 3,
+                height: 4
+            }),
+            g.get(3, 0)
+        );
+        assert!(g.get(0, 4).is_err());
+        assert!(g.get_signed(-1, 0).is_err());
+        assert!(g.get_signed(0, -1).is_err());
+        assert!(!g.contains(-1, 2));
+        assert!(g.contains(1, 2));
+
+        *g.get_signed_mut(1, 2).unwrap() = '#';
+        assert_eq!('#', g[(1, 2)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let g = parse(EXAMPLE).unwrap();
+
+        let rows = g
+            .rows()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["#..", ".#.", "..#", "#.#"], rows);
+        assert_eq!(Some(&['.', '.', '#'][..]), g.row(2));
+        assert_eq!(None, g.row(4));
+
+        let columns = g
+            .columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["#..#", ".#..", "..##"], columns);
+        assert_eq!(0, g.column(3).count());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = parse(EXAMPLE).unwrap();
+
+        let n = g.neighbors(0, 0).map(|(c, _)| c).collect::<Vec<_>>();
+        assert_eq!(vec![(1, 0), (0, 1)], n);
+
+        let n = g.neighbors(1, 1).map(|(c, _)| c).collect::<Vec<_>>();
+        assert_eq!(vec![(1, 0), (2, 1), (1, 2), (0, 1)], n);
+
+        let n = g
+            .neighbors_diagonal(1, 1)
+            .filter(|(_, t)| **t == '#')
+            .count();
+        assert_eq!(2, n);
+        assert_eq!(3, g.neighbors_diagonal(2, 3).count());
+    }
+
+    #[test]
+    fn test_positions() {
+        let g = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            vec![(0, 0), (1, 1), (2, 2), (0, 3), (2, 3)],
+            g.positions(|t| *t == '#').collect::<Vec<_>>()
+        );
+        assert_eq!(Some((1, 0)), g.position(|t| *t == '.'));
+        assert_eq!(5, g.map(|t| *t == '#').iter().filter(|(_, t)| **t).count());
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut g = Grid::<u8>::new(3, 2);
+        g[(1, 1)] = 1;
+        g[(2, 0)] = 2;
+
+        // taller keeps everything
+        g.resize(3, 4);
+        assert_eq!("002\n010\n000\n000\n", g.to_string());
+
+        // wider and shorter
+        g.resize(4, 1);
+        assert_eq!("0020\n", g.to_string());
+
+        g.resize(1, 2);
+        assert_eq!("0\n0\n", g.to_string());
+    }
+}
@@ -0,0 +1,106 @@
+// Just enough HTML handling for the pages the site serves, which are simple
+// and consistently formatted.
+
+// between returns the text between the first end and the closest start before
+// it, so that a repeated start doesn't swallow the text in between
+pub(crate) fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (before, _) = s.split_once(end)?;
+    let (_, inner) = before.rsplit_once(start)?;
+    Some(inner)
+}
+
+// elements returns the contents of every element opened with start and closed
+// with end, along with the offset in s where each starts
+pub(crate) fn elements<'a>(s: &'a str, start: &'a str, end: &'a str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+    while let Some(i) = s[offset..].find(start) {
+        let begin = offset + i + start.len();
+        let Some(len) = s[begin..].find(end) else {
+            break;
+        };
+        found.push((begin, &s[begin..begin + len]));
+        offset = begin + len + end.len();
+    }
+
+    found
+}
+
+// text removes tags and decodes entities
+pub(crate) fn text(html: &str) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => (),
+        }
+    }
+
+    unescape(&stripped)
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                e if e.starts_with("#x") => char::from_u32(u32::from_str_radix(&e[2..], 16).ok()?)?,
+                e if e.starts_with('#') => char::from_u32(e[1..].parse().ok()?)?,
+                _ => return None,
+            };
+            Some((c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "a <b> & \"c\" 'd' e",
+            text(
+                "a &lt;b&gt; &amp; &quot;<em>c</em>&quot; &#39;d&#x27; <span class=\"x\">e</span>"
+            )
+        );
+        assert_eq!("AT&T; & x;", text("AT&T; &amp; x;"));
+    }
+
+    #[test]
+    fn test_elements() {
+        assert_eq!(
+            vec![(4, "1"), (19, "2")],
+            elements("<em>1</em> and <em>2</em> <em>", "<em>", "</em>")
+        );
+        assert_eq!(Some("b"), between("a <p>a <p>b</p>", "<p>", "</p>"));
+    }
+}
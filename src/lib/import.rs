@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fixtures::{self, Fixture};
+use crate::html::{self, between, elements};
+use crate::prelude::*;
+
+// Page is a saved puzzle description: the year and day from its title, and its
+// articles, one for each part that was unlocked when it was saved.
+#[derive(Debug)]
+pub struct Page<'a> {
+    pub year: u32,
+    pub day: u32,
+    articles: Vec<&'a str>,
+}
+
+impl<'a> Page<'a> {
+    pub fn parse(html: &'a str) -> Result<Self> {
+        // <title>Day 9 - Advent of Code 2023</title>
+        let title =
+            between(html, "<title>", "</title>").ok_or_else(|| anyhow!("page has no title"))?;
+        let (day, year) = title
+            .strip_prefix("Day ")
+            .and_then(|t| t.split_once(" - Advent of Code "))
+            .ok_or_else(|| anyhow!("'{title}' isn't a puzzle page"))?;
+
+        let articles = elements(html, "<article", "</article>")
+            .into_iter()
+            .map(|(_, a)| a)
+            .collect::<Vec<_>>();
+        ensure!(!articles.is_empty(), "page has no puzzle description");
+
+        Ok(Self {
+            year: year.trim().parse().context("invalid year in title")?,
+            day: day.trim().parse().context("invalid day in title")?,
+            articles,
+        })
+    }
+
+    // examples returns the examples in the description with the answers given
+    // for them. The page's formatting is consistent but this still guesses, so
+    // check what it finds:
+    //
+    //  * part 1's first code block is its example. Later blocks, and any block
+    //    in part 2, are only examples if they don't highlight anything and the
+    //    paragraph introducing them mentions an example which isn't "above".
+    //  * each example's answer is the last highlighted code before the next
+    //    example, e.g. <code><em>114</em></code>.
+    //  * part 2's answer goes to part 1's first example if part 2 has none of
+    //    its own.
+    pub fn examples(&self) -> Result<Vec<Fixture>> {
+        let mut fixtures: Vec<Fixture> = vec![];
+
+        for (part, article) in self.articles.iter().take(2).enumerate() {
+            let starts = elements(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .enumerate()
+                .filter(|(i, (start, block))| {
+                    (part == 0 && *i == 0) || is_new_example(article, *start, block)
+                })
+                .map(|(_, (start, block))| (start, block))
+                .collect::<Vec<_>>();
+
+            if starts.is_empty() {
+                // the part reuses an earlier example
+                if let (Some(first), Some(answer)) = (fixtures.first_mut(), last_answer(article)) {
+                    first.answers[part] = Some(answer.parse()?);
+                }
+                continue;
+            }
+
+            for (i, (start, block)) in starts.iter().enumerate() {
+                let end = starts.get(i + 1).map_or(article.len(), |(s, _)| *s);
+                let mut input = html::text(block);
+                if !input.ends_with('\n') {
+                    input.push('\n');
+                }
+
+                let mut answers = [None, None];
+                if let Some(answer) = last_answer(&article[*start..end]) {
+                    answers[part] = Some(answer.parse()?);
+                }
+
+                match fixtures.iter_mut().find(|f| f.input == input) {
+                    Some(f) => f.answers[part] = answers[part].take(),
+                    None => fixtures.push(Fixture {
+                        input,
+                        answers,
+                        elisp_part1: None,
+                        ignore: None,
+                    }),
+                }
+            }
+        }
+
+        fixtures.retain(|f| f.answers.iter().any(Option::is_some));
+        ensure!(!fixtures.is_empty(), "found no examples with answers");
+        Ok(fixtures)
+    }
+}
+
+// is_new_example guesses whether the code block at start is a new example
+// rather than a diagram of one
+fn is_new_example(article: &str, start: usize, block: &str) -> bool {
+    if block.contains("<em>") {
+        return false;
+    }
+
+    let before = &article[..start];
+    let intro = before
+        .rfind("<p>")
+        .map_or(before, |i| &before[i..])
+        .to_lowercase();
+
+    intro.contains("example") && !intro.contains("above")
+}
+
+// last_answer returns the text of the last highlighted code in html
+fn last_answer(html: &str) -> Option<String> {
+    elements(html, "<code><em>", "</em></code>")
+        .into_iter()
+        .chain(elements(html, "<em><code>", "</code></em>"))
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, answer)| html::text(answer))
+}
+
+// import reads a saved puzzle page and writes its examples as fixtures named
+// example.toml, example-2.toml, and so on under dir. Existing fixtures are
+// only replaced if overwrite is set. Returns the files written.
+pub fn import(page: &Path, dir: &Path, overwrite: bool) -> Result<Vec<PathBuf>> {
+    let html = fs::read_to_string(page)
+        .with_context(|| format!("failed to read page {}", page.display()))?;
+    let page = Page::parse(&html)?;
+    let examples = page.examples()?;
+
+    let dir = fixtures::dir(dir, page.year, page.day);
+    let paths = (1..=examples.len())
+        .map(|i| match i {
+            1 => dir.join("example.toml"),
+            i => dir.join(format!("example-{i}.toml")),
+        })
+        .collect::<Vec<_>>();
+
+    if !overwrite {
+        if let Some(existing) = paths.iter().find(|p| p.exists()) {
+            bail!("{} already exists", existing.display());
+        }
+    }
+
+    fs::create_dir_all(&dir)?;
+    for (path, example) in paths.iter().zip(&examples) {
+        fs::write(path, example.to_toml())
+            .with_context(|| format!("failed to write fixture {}", path.display()))?;
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // trimmed down from a saved copy of 2023 day 9 after solving part 1
+    const PART_1: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head>
+<meta charset="utf-8"/>
+<title>Day 9 - Advent of Code 2023</title>
+</head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 9: Mirage Maintenance ---</h2><p>You ride the camel through the sandstorm...</p>
+<p>For example:</p>
+<pre><code>0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+</code></pre>
+<p>To extrapolate, start by making a new sequence from the difference at each step of your history:</p>
+<pre><code>0   3   6   9  12  15
+  3   3   3   3   3
+    0   0   0   0
+</code></pre>
+<p>Then, add a new value at the end:</p>
+<pre><code>0   3   6   9  12  15  <em>B</em>
+  3   3   3   3   3   <em>A</em>
+    0   0   0   0   <em>0</em>
+</code></pre>
+<p>So, the next value of the first history is <code><em>18</em></code>.</p>
+<p>If you find the next value for each history in this example and add them together, you get <code><em>114</em></code>.</p>
+<p>Analyze your OASIS report and extrapolate the next value for each history. <em>What is the sum of these extrapolated values?</em></p>
+</article>
+<p>Your puzzle answer was <code>2008960228</code>.</p>"#;
+
+    const PART_2: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Of course, it would be nice to have <em>even more history</em> included in your report.</p>
+<p>In particular, here is what the third example history looks like when extrapolating back in time:</p>
+<pre><code><em>5</em>  10  13  16  21  30  45
+  <em>5</em>   3   3   5   9  15
+</code></pre>
+<p>Adding the new values on the left side of each sequence from bottom to top eventually reveals the new left-most history value: <code><em>5</em></code>.</p>
+<p>Doing this for the remaining example data above results in previous values of <code><em>-3</em></code> for the first history and <code><em>0</em></code> for the second history. Adding all three new values together produces <code><em>2</em></code>.</p>
+</article>
+</main>
+</body>
+</html>"#;
+
+    // a part 2 with examples of its own, like 2023 day 8
+    const NEW_EXAMPLES: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>The sandstorm is upon you...</p>
+<p>For example:</p>
+<pre><code>LR
+
+11A = (11B, XXX)
+11Z = (11B, XXX)
+</code></pre>
+<p>Here, there are two starting nodes. So, in this example, you end up entirely on nodes that end in <code>Z</code> after <code><em>6</em></code> steps.</p>
+<p>Here's another example, with &quot;quotes&quot; &amp; &lt;brackets&gt;:</p>
+<pre><code>&lt;&gt;&amp;
+</code></pre>
+<p>This one takes <em><code>7</code></em> steps.</p>
+</article>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let page = Page::parse(PART_1).unwrap();
+        assert_eq!((2023, 9), (page.year, page.day));
+
+        assert!(Page::parse("<title>Advent of Code 2023</title>").is_err());
+        assert!(Page::parse("<title>Day 9 - Advent of Code 2023</title>").is_err());
+    }
+
+    #[test]
+    fn test_examples_part_1() {
+        let examples = Page::parse(PART_1).unwrap().examples().unwrap();
+
+        assert_eq!(1, examples.len());
+        assert_eq!(
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+            examples[0].input
+        );
+        assert_eq!([Some(Answer::Int(114)), None], examples[0].answers);
+    }
+
+    #[test]
+    fn test_examples_reused_in_part_2() {
+        let html = format!("{PART_1}\n{PART_2}");
+        let examples = Page::parse(&html).unwrap().examples().unwrap();
+
+        assert_eq!(1, examples.len());
+        assert_eq!(
+            [Some(Answer::Int(114)), Some(Answer::Int(2))],
+            examples[0].answers
+        );
+    }
+
+    #[test]
+    fn test_examples_new_in_part_2() {
+        let html = format!("{PART_1}\n{NEW_EXAMPLES}");
+        let examples = Page::parse(&html).unwrap().examples().unwrap();
+
+        assert_eq!(3, examples.len());
+        assert_eq!([Some(Answer::Int(114)), None], examples[0].answers);
+        assert_eq!(
+            "LR\n\n11A = (11B, XXX)\n11Z = (11B, XXX)\n",
+            examples[1].input
+        );
+        assert_eq!([None, Some(Answer::Int(6))], examples[1].answers);
+        assert_eq!("<>&\n", examples[2].input);
+        assert_eq!([None, Some(Answer::Int(7))], examples[2].answers);
+    }
+
+    #[test]
+    fn test_import() {
+        let dir = std::env::temp_dir().join(format!("aoc-import-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let page = dir.join("page.html");
+        fs::write(&page, format!("{PART_1}\n{PART_2}")).unwrap();
+
+        let written = import(&page, &dir, false).unwrap();
+        assert_eq!(vec![dir.join("2023/9/example.toml")], written);
+        assert_eq!(
+            Page::parse(&fs::read_to_string(&page).unwrap())
+                .unwrap()
+                .examples()
+                .unwrap()[0],
+            Fixture::load(&written[0]).unwrap()
+        );
+
+        assert!(import(&page, &dir, false).is_err());
+        assert!(import(&page, &dir, true).is_ok());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
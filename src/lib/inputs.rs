@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::client::Client;
+use crate::prelude::*;
+
+// DEFAULT_DIR is where puzzle inputs are stored, relative to the repository root.
+pub const DEFAULT_DIR: &str = "inputs";
+
+// path returns where the input for a year and day lives under dir: <dir>/<year>/<day>.txt
+pub fn path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string()).join(format!("{day}.txt"))
+}
+
+// Store reads inputs from a directory. If it has a client, missing inputs are
+// downloaded and saved. Inputs already in the store are never fetched again.
+pub struct Store<'a> {
+    dir: PathBuf,
+    client: Option<&'a Client>,
+}
+
+impl<'a> Store<'a> {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            client: None,
+        }
+    }
+
+    pub fn with_client(self, client: &'a Client) -> Self {
+        Self {
+            client: Some(client),
+            ..self
+        }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        path(&self.dir, year, day)
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Result<String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return fs::read_to_string(&path)
+                .with_context(|| format!("failed to read input {}", path.display()));
+        }
+
+        let client = self.client.ok_or_else(|| {
+            anyhow!(
+                "no input at {} and no session token is configured to fetch it",
+                path.display()
+            )
+        })?;
+        let input = client.input(year, day)?;
+
+        // write then rename so that an interrupted save is never mistaken
+        // for a cached input
+        let tmp = path.with_extension("txt.tmp");
+        fs::create_dir_all(self.dir.join(year.to_string()))?;
+        fs::write(&tmp, &input)?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("failed to save input to {}", path.display()))?;
+        info!("saved input to {}", path.display());
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{test_server::TestServer, Http};
+
+    fn store_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_once() {
+        let dir = store_dir("fetch");
+        let server = TestServer::start(vec![(200, "1 2 3\n")]);
+        let client = Client::new(Http::with_base(&server.base, "abc123"));
+        let store = Store::new(&dir).with_client(&client);
+
+        assert_eq!("1 2 3\n", store.get(2023, 9).unwrap());
+        assert_eq!("1 2 3\n", store.get(2023, 9).unwrap());
+        assert_eq!(
+            "1 2 3\n",
+            fs::read_to_string(dir.join("2023").join("9.txt")).unwrap()
+        );
+
+        let reqs = server.requests();
+        assert_eq!(1, reqs.len());
+        assert_eq!("/2023/day/9/input", reqs[0].path);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_failure_not_cached() {
+        let dir = store_dir("failure");
+        let server = TestServer::start(vec![(500, "oops")]);
+        let client = Client::new(Http::with_base(&server.base, "abc123"));
+        let store = Store::new(&dir).with_client(&client);
+
+        assert!(store.get(2023, 9).is_err());
+        assert!(!store.path(2023, 9).exists());
+        assert!(!store.path(2023, 9).with_extension("txt.tmp").exists());
+    }
+
+    #[test]
+    fn test_no_client() {
+        let dir = store_dir("offline");
+        fs::create_dir_all(dir.join("2022")).unwrap();
+        fs::write(dir.join("2022").join("6.txt"), "abc").unwrap();
+        let store = Store::new(&dir);
+
+        assert_eq!("abc", store.get(2022, 6).unwrap());
+        assert!(store.get(2022, 7).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,14 +1,36 @@
+pub mod answer;
+pub mod client;
+pub mod config;
 pub mod coord;
+pub mod coord3d;
+pub mod cycle;
+pub mod fixtures;
+pub mod grid;
+mod html;
+pub mod import;
+pub mod inputs;
 #[cfg(test)]
 mod logging;
-pub mod map;
 pub mod neighbor_map;
+pub mod profile;
+pub mod range_set;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solver;
+pub mod sparse_grid;
+pub mod submit;
+pub mod verify;
+pub mod watch;
 pub mod year_2022;
 pub mod year_2023;
 
 pub mod prelude {
+    pub use crate::answer::Answer;
     #[cfg(test)]
     pub use crate::logging::init as init_logging;
+    pub use crate::register;
+    pub use crate::solver::Solver;
     pub use anyhow::{anyhow, bail, ensure, Context, Error, Result};
     pub use derive_deref::{Deref, DerefMut};
     pub use log::{debug, error, info, trace, warn};
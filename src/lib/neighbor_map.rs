@@ -1,51 +1,129 @@
-#![allow(dead_code)]
-use crate::prelude::*;
-use std::{
-    cell::{self as cell, RefCell},
-    fmt,
-    rc::Rc,
+use crate::{
+    coord::{Coordinate, Direction, Orientation},
+    prelude::*,
 };
+use std::fmt;
+
+// Id is where a tile is in its map's arena
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id(usize);
+
+// Links are the ids of a tile's neighbors. They start as every adjacent tile
+// in the map, and can be cut to restrict where a tile leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Links {
+    pub up: Option<Id>,
+    pub right: Option<Id>,
+    pub down: Option<Id>,
+    pub left: Option<Id>,
+
+    pub upright: Option<Id>,
+    pub downright: Option<Id>,
+    pub downleft: Option<Id>,
+    pub upleft: Option<Id>,
+}
+
+impl Links {
+    pub fn get(&self, d: Direction) -> Option<Id> {
+        match d {
+            Direction::Up => self.up,
+            Direction::UpRight => self.upright,
+            Direction::Right => self.right,
+            Direction::DownRight => self.downright,
+            Direction::Down => self.down,
+            Direction::DownLeft => self.downleft,
+            Direction::Left => self.left,
+            Direction::UpLeft => self.upleft,
+        }
+    }
 
-// wraps a map point, providing helpers to interact with sibling nodes
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct Node<T>(Rc<RefCell<NodeData<T>>>);
+    pub fn get_mut(&mut self, d: Direction) -> &mut Option<Id> {
+        match d {
+            Direction::Up => &mut self.up,
+            Direction::UpRight => &mut self.upright,
+            Direction::Right => &mut self.right,
+            Direction::DownRight => &mut self.downright,
+            Direction::Down => &mut self.down,
+            Direction::DownLeft => &mut self.downleft,
+            Direction::Left => &mut self.left,
+            Direction::UpLeft => &mut self.upleft,
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct NodeData<T> {
-    x: usize,
-    y: usize,
-    inner: T,
-    neighbors: Neighbors<T>,
+    // returns an iterator over present links
+    pub fn iter(&self) -> impl Iterator<Item = Id> {
+        [
+            self.up,
+            self.right,
+            self.down,
+            self.left,
+            self.upright,
+            self.downright,
+            self.downleft,
+            self.upleft,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    // clear_diagonals cuts the links to diagonal neighbors
+    pub fn clear_diagonals(&mut self) {
+        self.upright = None;
+        self.downright = None;
+        self.downleft = None;
+        self.upleft = None;
+    }
 }
 
-#[derive(Clone, PartialEq, Eq, Default)]
-pub struct Neighbors<T> {
-    pub up: Option<Node<T>>,
-    pub right: Option<Node<T>>,
-    pub down: Option<Node<T>>,
-    pub left: Option<Node<T>>,
-
-    pub upright: Option<Node<T>>,
-    pub downright: Option<Node<T>>,
-    pub downleft: Option<Node<T>>,
-    pub upleft: Option<Node<T>>,
+// Node is a tile in a map, providing helpers to interact with sibling nodes.
+// It's only a reference to the map, so it's cheap to copy.
+pub struct Node<'a, T> {
+    map: &'a Map<T>,
+    id: Id,
 }
 
-impl<T> Neighbors<T> {
-    // returns an iteterator over present neighbors
-    pub fn iter(&self) -> impl Iterator<Item = &Node<T>> {
-        vec![
-            &self.up,
-            &self.right,
-            &self.down,
-            &self.left,
-            &self.upright,
-            &self.downright,
-            &self.downleft,
-            &self.upleft,
+// Neighbors are the nodes a tile links to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Neighbors<'a, T> {
+    pub up: Option<Node<'a, T>>,
+    pub right: Option<Node<'a, T>>,
+    pub down: Option<Node<'a, T>>,
+    pub left: Option<Node<'a, T>>,
+
+    pub upright: Option<Node<'a, T>>,
+    pub downright: Option<Node<'a, T>>,
+    pub downleft: Option<Node<'a, T>>,
+    pub upleft: Option<Node<'a, T>>,
+}
+
+impl<'a, T> Neighbors<'a, T> {
+    pub fn get(&self, d: Direction) -> Option<Node<'a, T>> {
+        match d {
+            Direction::Up => self.up,
+            Direction::UpRight => self.upright,
+            Direction::Right => self.right,
+            Direction::DownRight => self.downright,
+            Direction::Down => self.down,
+            Direction::DownLeft => self.downleft,
+            Direction::Left => self.left,
+            Direction::UpLeft => self.upleft,
+        }
+    }
+
+    // returns an iterator over present neighbors
+    pub fn iter(&self) -> impl Iterator<Item = Node<'a, T>> {
+        [
+            self.up,
+            self.right,
+            self.down,
+            self.left,
+            self.upright,
+            self.downright,
+            self.downleft,
+            self.upleft,
         ]
         .into_iter()
-        .filter_map(|n| n.as_ref())
+        .flatten()
     }
 
     pub fn len(&self) -> usize {
@@ -57,23 +135,10 @@ impl<T> Neighbors<T> {
     }
 }
 
-impl<T> Node<T> {
-    pub fn borrow(&self) -> cell::Ref<'_, NodeData<T>> {
-        self.0.borrow()
-    }
-
-    pub fn borrow_mut(&self) -> cell::RefMut<'_, NodeData<T>> {
-        self.0.borrow_mut()
-    }
-}
-
-// custom impl to avoid recursively debugging every node in the map
-impl<T: fmt::Debug> fmt::Debug for Neighbors<T> {
+// custom impl to avoid debugging every neighbor's whole map
+impl<T> fmt::Debug for Neighbors<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let fmt_node = |n: &Option<Node<T>>| {
-            n.as_ref()
-                .map(|n| format!("({}, {})", n.0.borrow().x, n.0.borrow().y))
-        };
+        let fmt_node = |n: &Option<Node<T>>| n.map(|n| (n.x(), n.y()));
         f.debug_struct("Neighbors")
             .field("up", &fmt_node(&self.up))
             .field("right", &fmt_node(&self.right))
@@ -87,109 +152,207 @@ impl<T: fmt::Debug> fmt::Debug for Neighbors<T> {
     }
 }
 
-impl<T> NodeData<T> {
-    pub fn neighbors(&self) -> &Neighbors<T> {
-        &self.neighbors
+impl<'a, T> Node<'a, T> {
+    pub fn id(&self) -> Id {
+        self.id
     }
 
-    pub fn neighbors_mut(&mut self) -> &mut Neighbors<T> {
-        &mut self.neighbors
+    // gets a reference to the interior value
+    pub fn value(&self) -> &'a T {
+        &self.map.tiles[self.id.0]
     }
 
-    // sets the interior value, returning the previous one
-    pub fn set(&mut self, new: T) -> T {
-        std::mem::replace(&mut self.inner, new)
+    pub fn x(&self) -> usize {
+        self.id.0 % self.map.width
     }
 
-    // gets a reference to the interior value
-    pub fn value(&self) -> &T {
-        &self.inner
+    pub fn y(&self) -> usize {
+        self.id.0 / self.map.width
     }
 
-    pub fn x(&self) -> usize {
-        self.x
+    pub fn links(&self) -> Links {
+        self.map.links[self.id.0]
     }
 
-    pub fn y(&self) -> usize {
-        self.y
+    pub fn neighbors(&self) -> Neighbors<'a, T> {
+        let links = self.links();
+        let node = |id: Option<Id>| id.map(|id| self.map.node(id));
+        Neighbors {
+            up: node(links.up),
+            right: node(links.right),
+            down: node(links.down),
+            left: node(links.left),
+            upright: node(links.upright),
+            downright: node(links.downright),
+            downleft: node(links.downleft),
+            upleft: node(links.upleft),
+        }
     }
 }
 
-// Map is a safe, densely allocated grid of nodes.
-// Nodes contain references to sibling nodes in the grid.
+// derived impls would require T: Clone, but only the reference is copied
+impl<T> Clone for Node<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Node<'_, T> {}
+
+// nodes are equal if they're the same tile in the same map
+impl<T> PartialEq for Node<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.map, other.map) && self.id == other.id
+    }
+}
+
+impl<T> Eq for Node<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Node<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("x", &self.x())
+            .field("y", &self.y())
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+// Map is a densely allocated grid of tiles, each linked to the tiles around it.
+// Tiles and their links are stored in arenas indexed by Id, so maps can be
+// shared between threads. y = 0 is the bottom row, see ORIENTATION.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Map<T> {
     // y --> x --> T
-    tiles: Vec<Vec<Node<T>>>,
+    tiles: Vec<T>,
+    links: Vec<Links>,
     width: usize,
     height: usize,
 }
 
+// Neighborhood is which tiles around a tile it's linked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighborhood {
+    // up, right, down and left
+    VonNeumann,
+    // von Neumann plus the diagonals
+    #[default]
+    Moore,
+    // six neighbors in axial coordinates: x runs east and y runs north east, so
+    // up is north east, down is south west, upleft is north west and downright
+    // is south east.
+    Hex,
+}
+
+impl Neighborhood {
+    pub fn directions(self) -> &'static [Direction] {
+        use Direction::*;
+        match self {
+            Neighborhood::VonNeumann => &Direction::CARDINAL,
+            Neighborhood::Moore => &Direction::ALL,
+            Neighborhood::Hex => &[Up, Right, DownRight, Down, Left, UpLeft],
+        }
+    }
+}
+
+// EdgeFn maps a link leaving the map from a tile to the tile it arrives at,
+// or None if it goes nowhere. It's called with the tile's coordinates and the
+// out of bounds coordinates the link would reach.
+pub type EdgeFn = dyn Fn((usize, usize), (i64, i64)) -> Option<(usize, usize)>;
+
+// Topology is what happens to links which leave the map
+#[derive(Default)]
+pub enum Topology {
+    // links off the map are dropped
+    #[default]
+    Bounded,
+    // links off the map wrap around to the other side
+    Torus,
+    // links off the map go wherever the function says, e.g. for folding
+    // a map into a cube
+    Custom(Box<EdgeFn>),
+}
+
+impl fmt::Debug for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Bounded => write!(f, "Bounded"),
+            Topology::Torus => write!(f, "Torus"),
+            Topology::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
 impl<T: Default + Clone> Map<T> {
-    // new initializes the datastore then goes back to populate neighbor
-    // values since they're only present after initialization
+    // new creates a bounded map of default tiles, each linked to all eight tiles
+    // around it
     pub fn new(width: usize, height: usize) -> Self {
-        let mut tiles = vec![vec![]; height];
+        Self::with_neighborhood(width, height, Neighborhood::Moore, Topology::Bounded)
+            .expect("bounded maps only link tiles in bounds")
+    }
 
-        for y in 0..height {
-            let row = tiles.get_mut(y).unwrap();
-            for x in 0..width {
-                row.push(Node(Rc::new(RefCell::new(NodeData {
+    // with_neighborhood creates a map of default tiles, each linked to its
+    // neighborhood. Links off the edge follow the topology. An error is returned if
+    // a custom topology links to a tile which isn't in the map.
+    pub fn with_neighborhood(
+        width: usize,
+        height: usize,
+        neighborhood: Neighborhood,
+        topology: Topology,
+    ) -> Result<Self, MapError> {
+        let (w, h) = (width as i64, height as i64);
+        let link = |x: usize, y: usize, d: Direction| -> Result<Option<Id>, MapError> {
+            let to = Coordinate::new(x as i64, y as i64) + d.delta(Self::ORIENTATION);
+            let to = if (0..w).contains(&to.x) && (0..h).contains(&to.y) {
+                Some((to.x as usize, to.y as usize))
+            } else {
+                match &topology {
+                    Topology::Bounded => None,
+                    Topology::Torus => {
+                        Some((to.x.rem_euclid(w) as usize, to.y.rem_euclid(h) as usize))
+                    }
+                    Topology::Custom(f) => f((x, y), to.into()),
+                }
+            };
+
+            match to {
+                Some((x, y)) if x >= width || y >= height => Err(MapError::OutOfBounds {
                     x,
                     y,
-                    ..Default::default()
-                }))));
+                    width,
+                    height,
+                }),
+                Some((x, y)) => Ok(Some(Id(y * width + x))),
+                None => Ok(None),
             }
-        }
-
-        let ret = Self {
-            tiles,
-            width,
-            height,
         };
 
+        let mut links = Vec::with_capacity(width * height);
         for y in 0..height {
             for x in 0..width {
-                let mut node = ret.get_mut(x, y).unwrap();
-
-                node.x = x;
-                node.y = y;
-                if x > 0 {
-                    // left
-                    node.neighbors.left = ret.get_node(x - 1, y).ok().cloned();
-                }
-                if y > 0 {
-                    // down
-                    node.neighbors.down = ret.get_node(x, y - 1).ok().cloned();
-                }
-                // right
-                node.neighbors.right = ret.get_node(x + 1, y).ok().cloned();
-                // up
-                node.neighbors.up = ret.get_node(x, y + 1).ok().cloned();
-
-                node.neighbors.upright = ret.get_node(x + 1, y + 1).ok().cloned();
-                if y > 0 {
-                    node.neighbors.downright = ret.get_node(x + 1, y - 1).ok().cloned();
-                    if x > 0 {
-                        node.neighbors.downleft = ret.get_node(x - 1, y - 1).ok().cloned();
-                    }
-                }
-                if x > 0 {
-                    node.neighbors.upleft = ret.get_node(x - 1, y + 1).ok().cloned();
+                let mut l = Links::default();
+                for d in neighborhood.directions() {
+                    *l.get_mut(*d) = link(x, y, *d)?;
                 }
+                links.push(l);
             }
         }
 
-        ret
+        Ok(Self {
+            tiles: vec![T::default(); width * height],
+            links,
+            width,
+            height,
+        })
     }
 }
 
 impl<T: fmt::Display> fmt::Display for Map<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // we're stored with y=0 first, walk numbers instead of using iter
-        for y in (0..self.height).rev() {
-            for x in 0..self.width {
-                write!(f, "{}", self.get(x, y).unwrap().inner)?;
+        // we're stored with y=0 first, walk rows backwards
+        for row in self.tiles.chunks(self.width.max(1)).rev() {
+            for t in row {
+                write!(f, "{t}")?;
             }
             writeln!(f)?;
         }
@@ -198,7 +361,7 @@ impl<T: fmt::Display> fmt::Display for Map<T> {
     }
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum MapError {
     #[error("specified coordinate ({x}, {y}) is out of bounds: width={width}, height={height}")]
     OutOfBounds {
@@ -207,11 +370,11 @@ pub enum MapError {
         width: usize,
         height: usize,
     },
-    #[error("requested node at ({0}, {1}) is already borrowed exclusively")]
-    DoubleBorrow(usize, usize),
 }
 
 impl<T> Map<T> {
+    pub const ORIENTATION: Orientation = Orientation::YUp;
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -220,50 +383,11 @@ impl<T> Map<T> {
         self.height
     }
 
-    // iter walks each item in each row, bottom to top, left to right, borrowing the node data
-    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), cell::Ref<'_, NodeData<T>>)> {
-        self.tiles.iter().enumerate().flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(x, node)| ((x, y), node.borrow()))
-        })
-    }
-
-    // iter_mut is like iter, but walks a mutable reference of the inner node data.
-    pub fn iter_mut(
-        &self,
-    ) -> impl Iterator<Item = ((usize, usize), cell::RefMut<'_, NodeData<T>>)> {
-        self.tiles.iter().enumerate().flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(x, node)| ((x, y), node.borrow_mut()))
-        })
-    }
-
-    // iterates over the nodes in
-    pub fn iter_nodes(&self) -> impl Iterator<Item = ((usize, usize), &Node<T>)> {
-        self.tiles
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, node)| ((x, y), node)))
+    fn node(&self, id: Id) -> Node<'_, T> {
+        Node { map: self, id }
     }
 
-    // return the interior refcell borrowed for easier API use. Node is transparent
-    // unless explicitly requested.
-    //
-    // panics if the node is borrowed mutably.
-    pub fn get(&self, x: usize, y: usize) -> Result<cell::Ref<'_, NodeData<T>>, MapError> {
-        self.get_node(x, y)?
-            .0
-            .try_borrow()
-            .map_err(|_| MapError::DoubleBorrow(x, y))
-    }
-
-    // return the interior refcell borrowed for easier API use. Node is transparent
-    // unless explicitly requested.
-    //
-    // panics if the node is borrowed mutably.
-    pub fn get_node(&self, x: usize, y: usize) -> Result<&Node<T>, MapError> {
+    fn id(&self, x: usize, y: usize) -> Result<Id, MapError> {
         if y >= self.height || x >= self.width {
             return Err(MapError::OutOfBounds {
                 x,
@@ -273,16 +397,39 @@ impl<T> Map<T> {
             });
         }
 
-        Ok(self.tiles.get(y).and_then(|row| row.get(x)).unwrap())
+        Ok(Id(y * self.width + x))
     }
 
-    // return the interior refcell borrowed mutably for easier API use. If we returned the Node,
-    // we'd have to lock the entire row exclusively.
-    pub fn get_mut(&self, x: usize, y: usize) -> Result<cell::RefMut<'_, NodeData<T>>, MapError> {
-        self.get_node(x, y)?
-            .0
-            .try_borrow_mut()
-            .map_err(|_| MapError::DoubleBorrow(x, y))
+    // iter walks each node in each row, bottom to top, left to right
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), Node<'_, T>)> {
+        (0..self.tiles.len()).map(|i| {
+            let n = self.node(Id(i));
+            ((n.x(), n.y()), n)
+        })
+    }
+
+    // iter_mut is like iter, but walks a mutable reference to each tile
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.tiles
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, t)| ((i % width, i / width), t))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Result<Node<'_, T>, MapError> {
+        Ok(self.node(self.id(x, y)?))
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut T, MapError> {
+        let id = self.id(x, y)?;
+        Ok(&mut self.tiles[id.0])
+    }
+
+    // links_mut returns the links of the tile at (x, y) so they can be cut
+    pub fn links_mut(&mut self, x: usize, y: usize) -> Result<&mut Links, MapError> {
+        let id = self.id(x, y)?;
+        Ok(&mut self.links[id.0])
     }
 }
 
@@ -296,27 +443,142 @@ mod tests {
         let m = Map::<()>::new(5, 5);
 
         let n = m.get(0, 0).unwrap();
-        assert_eq!((0, 0), (n.x, n.y));
+        assert_eq!((0, 0), (n.x(), n.y()));
 
         let n = n.neighbors();
         assert_eq!(None, n.down);
         assert_eq!(None, n.left);
-        let right = n.right.as_ref().unwrap().borrow();
-        assert_eq!((1, 0), (right.x, right.y));
-        let up = n.up.as_ref().unwrap().borrow();
-        assert_eq!((0, 1), (up.x, up.y));
+        let right = n.right.unwrap();
+        assert_eq!((1, 0), (right.x(), right.y()));
+        let up = n.up.unwrap();
+        assert_eq!((0, 1), (up.x(), up.y()));
+        assert_eq!(3, n.len());
 
         let n = m.get(3, 3).unwrap();
-        assert_eq!((3, 3), (n.x, n.y));
+        assert_eq!((3, 3), (n.x(), n.y()));
 
         let n = n.neighbors();
-        let right = n.right.as_ref().unwrap().borrow();
-        assert_eq!((4, 3), (right.x, right.y));
-        let down = n.down.as_ref().unwrap().borrow();
-        assert_eq!((3, 2), (down.x, down.y));
-        let left = n.left.as_ref().unwrap().borrow();
-        assert_eq!((2, 3), (left.x, left.y));
-        let up = n.up.as_ref().unwrap().borrow();
-        assert_eq!((3, 4), (up.x, up.y));
+        let right = n.right.unwrap();
+        assert_eq!((4, 3), (right.x(), right.y()));
+        let down = n.down.unwrap();
+        assert_eq!((3, 2), (down.x(), down.y()));
+        let left = n.left.unwrap();
+        assert_eq!((2, 3), (left.x(), left.y()));
+        let up = n.up.unwrap();
+        assert_eq!((3, 4), (up.x(), up.y()));
+        let upleft = n.upleft.unwrap();
+        assert_eq!((2, 4), (upleft.x(), upleft.y()));
+        assert_eq!(8, n.len());
+
+        assert_eq!(3, m.get(4, 4).unwrap().neighbors().len());
+        assert!(m.get(5, 0).is_err());
+    }
+
+    #[test]
+    fn test_set_and_cut_links() {
+        let mut m = Map::<u8>::new(3, 2);
+        *m.get_mut(1, 1).unwrap() = 7;
+        m.links_mut(0, 0).unwrap().clear_diagonals();
+        m.links_mut(0, 0).unwrap().up = None;
+
+        assert_eq!("070\n000\n", m.to_string());
+        let n = m.get(0, 0).unwrap().neighbors();
+        assert_eq!(
+            vec![(1, 0)],
+            n.iter().map(|n| (n.x(), n.y())).collect::<Vec<_>>()
+        );
+
+        // links are one way
+        let up = m.get(0, 1).unwrap().neighbors().down.unwrap();
+        assert_eq!(m.get(0, 0).unwrap(), up);
+        assert_eq!(7, *m.get(0, 1).unwrap().neighbors().right.unwrap().value());
+    }
+
+    fn coords<T>(n: Option<Node<'_, T>>) -> Option<(usize, usize)> {
+        n.map(|n| (n.x(), n.y()))
+    }
+
+    #[test]
+    fn test_neighborhoods() {
+        let m = Map::<()>::with_neighborhood(3, 3, Neighborhood::VonNeumann, Topology::Bounded)
+            .unwrap();
+        assert_eq!(4, m.get(1, 1).unwrap().neighbors().len());
+        assert_eq!(2, m.get(0, 0).unwrap().neighbors().len());
+        assert_eq!(None, m.get(1, 1).unwrap().neighbors().upright);
+
+        let m = Map::<()>::with_neighborhood(3, 3, Neighborhood::Hex, Topology::Bounded).unwrap();
+        let n = m.get(1, 1).unwrap().neighbors();
+        assert_eq!(6, n.len());
+        assert_eq!(Some((0, 2)), coords(n.upleft));
+        assert_eq!(Some((2, 0)), coords(n.downright));
+        assert_eq!(None, n.upright);
+        assert_eq!(None, n.downleft);
+
+        assert_eq!(
+            Map::<()>::new(4, 2),
+            Map::with_neighborhood(4, 2, Default::default(), Default::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_topologies() {
+        let m = Map::<()>::with_neighborhood(3, 2, Neighborhood::Moore, Topology::Torus).unwrap();
+        let n = m.get(0, 0).unwrap().neighbors();
+        assert_eq!(8, n.len());
+        assert_eq!(Some((2, 0)), coords(n.left));
+        assert_eq!(Some((0, 1)), coords(n.down));
+        assert_eq!(Some((2, 1)), coords(n.downleft));
+        assert_eq!(Some((1, 1)), coords(n.upright));
+
+        // a cylinder which wraps left and right, but not up and down
+        let cylinder = Topology::Custom(Box::new(|_, (x, y)| {
+            (0..2)
+                .contains(&y)
+                .then_some((x.rem_euclid(3) as usize, y as usize))
+        }));
+        let m = Map::<()>::with_neighborhood(3, 2, Neighborhood::VonNeumann, cylinder).unwrap();
+        let n = m.get(2, 1).unwrap().neighbors();
+        assert_eq!(Some((0, 1)), coords(n.right));
+        assert_eq!(None, n.up);
+        assert_eq!(3, n.len());
+
+        let broken = Topology::Custom(Box::new(|_, _| Some((3, 0))));
+        assert_eq!(
+            Err(MapError::OutOfBounds {
+                x: 3,
+                y: 0,
+                width: 3,
+                height: 2
+            }),
+            Map::<()>::with_neighborhood(3, 2, Neighborhood::VonNeumann, broken)
+        );
+    }
+
+    #[test]
+    fn test_parallel() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Map<u64>>();
+        assert_send_sync::<Node<'_, u64>>();
+
+        let mut m = Map::<u64>::new(64, 64);
+        for ((x, y), t) in m.iter_mut() {
+            *t = (x * y) as u64;
+        }
+
+        // sum each tile's neighbors, half the rows on each thread
+        let m = &m;
+        let sum = |rows: std::ops::Range<usize>| {
+            m.iter()
+                .filter(|((_, y), _)| rows.contains(y))
+                .map(|(_, n)| n.neighbors().iter().map(|n| *n.value()).sum::<u64>())
+                .sum::<u64>()
+        };
+        let total = std::thread::scope(|s| {
+            let low = s.spawn(|| sum(0..32));
+            let high = s.spawn(|| sum(32..64));
+            low.join().unwrap() + high.join().unwrap()
+        });
+
+        assert_eq!(sum(0..64), total);
     }
 }
@@ -0,0 +1,138 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use serde::Serialize;
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+// CountingAlloc is the system allocator with counters for profiling. It only
+// counts if it's installed as the global allocator, which the binary does:
+//
+//   #[global_allocator]
+//   static ALLOC: CountingAlloc = CountingAlloc;
+pub struct CountingAlloc;
+
+fn allocated(size: usize) {
+    let size = size as u64;
+    ALLOCS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size, Relaxed);
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+fn freed(size: usize) {
+    CURRENT.fetch_sub(size as u64, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            allocated(layout.size());
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            allocated(layout.size());
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    // a realloc counts as freeing the old allocation and making a new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        p
+    }
+}
+
+// Stats are the heap allocations made while measuring something.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub allocs: u64,
+    // total bytes allocated, including those freed since
+    pub bytes: u64,
+    // the most bytes in use at once, beyond what was in use at the start
+    pub peak: u64,
+}
+
+// measure runs f and returns the allocations made while it ran. Allocations by
+// other threads are included, and measurements shouldn't be nested since each
+// one resets the peak.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let allocs = ALLOCS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let start = CURRENT.load(Relaxed);
+    PEAK.store(start, Relaxed);
+
+    let result = f();
+
+    let stats = Stats {
+        allocs: ALLOCS.load(Relaxed) - allocs,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: PEAK.load(Relaxed).saturating_sub(start),
+    };
+    (result, stats)
+}
+
+// bytes formats a byte count for people, e.g. 1.5 MiB
+pub fn bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{n} B");
+    }
+
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+// the tests count their own allocations
+#[cfg(test)]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (v, stats) = measure(|| {
+            let big = vec![0u8; 1 << 20];
+            drop(big);
+            vec![1u8; 4096]
+        });
+
+        assert_eq!(4096, v.len());
+        // other tests run at the same time, so these are lower bounds
+        assert!(stats.allocs >= 2);
+        assert!(stats.bytes >= (1 << 20) + 4096);
+        assert!(stats.peak >= 1 << 20);
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!("12 B", bytes(12));
+        assert_eq!("1.5 KiB", bytes(1536));
+        assert_eq!("3.0 MiB", bytes(3 << 20));
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops::Range;
+
+use num::PrimInt;
+
+// RangeSet is a set of integers stored as the half open ranges they cover. The
+// ranges are kept sorted and merged, so no two overlap or touch and none are
+// empty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // ranges returns the merged ranges in ascending order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // total_len returns how many integers are in the set
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |acc, r| acc + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    pub fn contains(&self, v: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= v);
+        self.ranges.get(i).is_some_and(|r| r.start <= v)
+    }
+
+    // insert adds every integer in r, merging it with any ranges it overlaps or
+    // touches
+    pub fn insert(&mut self, r: Range<T>) {
+        if r.start >= r.end {
+            return;
+        }
+
+        let i = self.ranges.partition_point(|x| x.end < r.start);
+        let j = self.ranges.partition_point(|x| x.start <= r.end);
+        let merged = match (self.ranges[i..j].first(), self.ranges[i..j].last()) {
+            (Some(first), Some(last)) => first.start.min(r.start)..last.end.max(r.end),
+            _ => r,
+        };
+
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    // remove takes every integer in r out of the set, trimming or splitting the
+    // ranges it overlaps
+    pub fn remove(&mut self, r: Range<T>) {
+        if r.start >= r.end {
+            return;
+        }
+
+        let i = self.ranges.partition_point(|x| x.end <= r.start);
+        let j = self.ranges.partition_point(|x| x.start < r.end);
+        if i == j {
+            return;
+        }
+
+        let (first, last) = (self.ranges[i].clone(), self.ranges[j - 1].clone());
+        let left = (first.start < r.start).then(|| first.start..r.start);
+        let right = (r.end < last.end).then(|| r.end..last.end);
+
+        self.ranges.splice(i..j, left.into_iter().chain(right));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut s = self.clone();
+        s.extend(other.iter().cloned());
+        s
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut l, mut r) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(a), Some(b)) = (l.peek(), r.peek()) {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // whichever ends first can't overlap anything else
+            if a.end < b.end {
+                l.next();
+            } else {
+                r.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut s = self.clone();
+        for r in other.iter() {
+            s.remove(r.clone());
+        }
+        s
+    }
+}
+
+impl<T: PrimInt> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for r in iter {
+            self.insert(r);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut s = Self::new();
+        s.extend(iter);
+        s
+    }
+}
+
+// split cuts range at every boundary inside of it, returning the pieces in
+// order. Each piece is then wholly on one side of every boundary.
+pub fn split<T: PrimInt>(
+    range: Range<T>,
+    boundaries: impl IntoIterator<Item = T>,
+) -> Vec<Range<T>> {
+    if range.start >= range.end {
+        return vec![];
+    }
+
+    let mut cuts = boundaries
+        .into_iter()
+        .filter(|b| range.start < *b && *b < range.end)
+        .collect::<Vec<_>>();
+    cuts.sort();
+    cuts.dedup();
+
+    let mut pieces = Vec::with_capacity(cuts.len() + 1);
+    let mut start = range.start;
+    for cut in cuts {
+        pieces.push(start..cut);
+        start = cut;
+    }
+    pieces.push(start..range.end);
+
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // the brute force model of a set of ranges
+    fn model(ranges: &[Range<i64>]) -> HashSet<i64> {
+        ranges.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn assert_normalized(s: &RangeSet<i64>) {
+        for r in s.iter() {
+            assert!(r.start < r.end, "{r:?} is empty in {s:?}");
+        }
+        for pair in s.ranges().windows(2) {
+            assert!(
+                pair[0].end < pair[1].start,
+                "{pair:?} aren't merged in {s:?}"
+            );
+        }
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<i64>>> {
+        prop::collection::vec((-50_i64..50, 0_i64..20).prop_map(|(s, l)| s..s + l), 0..10)
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut s: RangeSet<u8> = [5..10, 0..2, 10..12, 20..20].into_iter().collect();
+        assert_eq!(&[0..2, 5..12], s.ranges());
+        assert_eq!(9, s.total_len());
+        assert_eq!((Some(0), Some(11)), (s.min(), s.max()));
+
+        s.remove(7..8);
+        assert_eq!(&[0..2, 5..7, 8..12], s.ranges());
+        s.insert(1..9);
+        assert_eq!(Some(&(0..12)), s.ranges().first());
+        assert_eq!(1, s.ranges().len());
+        s.remove(0..255);
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(vec![0..3, 3..5, 5..10], split(0..10, [5, 3, 12, 0, 3]));
+        assert_eq!(vec![0_u64..10], split(0..10, []));
+        assert!(split(4..4, [4]).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_insert(rs in ranges(), v in -60_i64..80) {
+            let s: RangeSet<i64> = rs.iter().cloned().collect();
+            let m = model(&rs);
+
+            assert_normalized(&s);
+            prop_assert_eq!(&m, &model(s.ranges()));
+            prop_assert_eq!(m.len() as i64, s.total_len());
+            prop_assert_eq!(m.contains(&v), s.contains(v));
+            prop_assert_eq!(m.iter().min().copied(), s.min());
+            prop_assert_eq!(m.iter().max().copied(), s.max());
+        }
+
+        #[test]
+        fn prop_set_operations(l in ranges(), r in ranges()) {
+            let (ls, rs): (RangeSet<i64>, RangeSet<i64>) =
+                (l.iter().cloned().collect(), r.iter().cloned().collect());
+            let (lm, rm) = (model(&l), model(&r));
+
+            let union = ls.union(&rs);
+            assert_normalized(&union);
+            prop_assert_eq!(&lm | &rm, model(union.ranges()));
+
+            let intersection = ls.intersection(&rs);
+            assert_normalized(&intersection);
+            prop_assert_eq!(&lm & &rm, model(intersection.ranges()));
+
+            let difference = ls.difference(&rs);
+            assert_normalized(&difference);
+            prop_assert_eq!(&lm - &rm, model(difference.ranges()));
+        }
+
+        #[test]
+        fn prop_split(s in -50_i64..50, l in 0_i64..40, cuts in prop::collection::vec(-60_i64..100, 0..8)) {
+            let pieces = split(s..s + l, cuts.iter().copied());
+
+            // the pieces are contiguous and cover the range exactly
+            prop_assert_eq!((s..s + l).collect::<HashSet<_>>(), model(&pieces));
+            prop_assert_eq!(l, pieces.iter().map(|p| p.end - p.start).sum::<i64>());
+            for pair in pieces.windows(2) {
+                prop_assert_eq!(pair[0].end, pair[1].start);
+            }
+
+            // and no piece straddles a cut
+            for p in &pieces {
+                prop_assert!(cuts.iter().all(|c| *c <= p.start || *c >= p.end));
+            }
+        }
+    }
+}
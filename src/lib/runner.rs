@@ -0,0 +1,415 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::prelude::*;
+use crate::profile::{self, Stats};
+use crate::solver::Puzzle;
+
+// Timed is the outcome of one phase of a solver along with how long it took
+// and what it allocated. Allocations are only counted if the global allocator
+// is profile::CountingAlloc.
+#[derive(Debug)]
+pub struct Timed<T> {
+    pub result: Result<T>,
+    pub elapsed: Duration,
+    pub alloc: Stats,
+}
+
+// timed runs f, turning a panic into an error so that one broken solver
+// doesn't take down the rest of a run.
+pub fn timed<T>(f: impl FnOnce() -> Result<T>) -> Timed<T> {
+    let start = Instant::now();
+    let (result, alloc) = profile::measure(|| {
+        catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|p| Err(panic_error(p)))
+    });
+
+    Timed {
+        result,
+        elapsed: start.elapsed(),
+        alloc,
+    }
+}
+
+fn panic_error(payload: Box<dyn Any + Send>) -> Error {
+    let msg = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string());
+
+    anyhow!("panicked: {msg}")
+}
+
+// Parts selects which parts of a puzzle are solved, e.g. to skip a slow one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    // numbers returns the part numbers selected, in order
+    pub fn numbers(self) -> &'static [u8] {
+        match self {
+            Parts::One => &[1],
+            Parts::Two => &[2],
+            Parts::Both => &[1, 2],
+        }
+    }
+}
+
+impl FromStr for Parts {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => bail!("unknown part '{s}', expected 1, 2, or both"),
+        }
+    }
+}
+
+// Run is the result of parsing an input and solving the selected parts of a
+// puzzle.
+#[derive(Debug)]
+pub struct Run {
+    pub puzzle: &'static Puzzle,
+    pub parse: Timed<()>,
+    // each part solved with its number, empty if parsing failed
+    pub parts: Vec<(u8, Timed<Answer>)>,
+}
+
+impl Run {
+    // returns the result for part 1 or 2, if it was solved
+    pub fn part(&self, part: u8) -> Option<&Timed<Answer>> {
+        self.parts.iter().find(|(p, _)| *p == part).map(|(_, t)| t)
+    }
+
+    // returns the parse error or any part's error, if anything failed
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        self.parse.result.as_ref().err().into_iter().chain(
+            self.parts
+                .iter()
+                .filter_map(|(_, t)| t.result.as_ref().err()),
+        )
+    }
+}
+
+// Report is a serializable summary of a run, e.g. for `--format json`:
+//
+//   {"year":2023,"day":9,"ok":true,"parse":{"ms":0.1,"error":null},
+//    "parts":[{"part":1,"answer":2008960228,"ms":1.2,"error":null}, ...]}
+//
+// parts is empty if parsing failed. Profiled reports also have each phase's
+// allocations as "alloc":{"allocs":12,"bytes":4096,"peak":2048}.
+#[derive(Debug, Serialize)]
+pub struct Report<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub ok: bool,
+    pub parse: Phase<'a>,
+    pub parts: Vec<Phase<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Phase<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<&'a Answer>,
+    pub ms: f64,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<Stats>,
+}
+
+impl Phase<'_> {
+    fn new<T>(t: &Timed<T>, profile: bool) -> Self {
+        Self {
+            part: None,
+            answer: None,
+            ms: t.elapsed.as_secs_f64() * 1000.0,
+            error: t.result.as_ref().err().map(|e| format!("{e:#}")),
+            alloc: profile.then_some(t.alloc),
+        }
+    }
+}
+
+impl Run {
+    // report summarizes the run, with allocations if profile is set
+    pub fn report(&self, profile: bool) -> Report<'_> {
+        Report {
+            year: self.puzzle.year,
+            day: self.puzzle.day,
+            ok: self.errors().next().is_none(),
+            parse: Phase::new(&self.parse, profile),
+            parts: self
+                .parts
+                .iter()
+                .map(|(part, t)| Phase {
+                    part: Some(*part),
+                    answer: t.result.as_ref().ok(),
+                    ..Phase::new(t, profile)
+                })
+                .collect(),
+        }
+    }
+}
+
+// run parses the input and solves both parts of the puzzle, timing each.
+pub fn run(puzzle: &'static Puzzle, input: &str) -> Run {
+    run_parts(puzzle, input, Parts::Both)
+}
+
+// run_parts parses the input and solves the selected parts, timing each.
+pub fn run_parts(puzzle: &'static Puzzle, input: &str, parts: Parts) -> Run {
+    let parsed = timed(|| puzzle.parse(input));
+    let parse = Timed {
+        elapsed: parsed.elapsed,
+        alloc: parsed.alloc,
+        result: parsed
+            .result
+            .as_ref()
+            .map(|_| ())
+            .map_err(|e| anyhow!("{e:#}")),
+    };
+
+    let parts = match &parsed.result {
+        Ok(p) => parts
+            .numbers()
+            .iter()
+            .map(|&part| match part {
+                1 => (part, timed(|| puzzle.part1(p))),
+                _ => (part, timed(|| puzzle.part2(p))),
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+
+    Run {
+        puzzle,
+        parse,
+        parts,
+    }
+}
+
+// Table renders runs as rows of answers and timings.
+pub struct Table<'a>(pub &'a [Run]);
+
+const HEADERS: [&str; 6] = ["puzzle", "parse", "part 1", "time", "part 2", "time"];
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        columns(f, HEADERS, &self.0.iter().map(row).collect::<Vec<_>>())
+    }
+}
+
+// columns writes rows under headers, with each column padded to fit
+fn columns<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    headers: [&str; N],
+    rows: &[[String; N]],
+) -> fmt::Result {
+    let mut widths = headers.map(str::len);
+    for r in rows {
+        for (w, cell) in widths.iter_mut().zip(r) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let headers = headers.map(str::to_string);
+    for r in std::iter::once(&headers).chain(rows) {
+        let line = r
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(f, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn row(r: &Run) -> [String; 6] {
+    let duration = |d: &Duration| format!("{d:.2?}");
+    let answer = |a: &Result<Answer>| match a {
+        Ok(a) if a.is_multiline() => "<multi-line>".to_string(),
+        Ok(a) => a.to_string(),
+        Err(_) => "FAILED".to_string(),
+    };
+
+    let mut cells = [
+        r.puzzle.to_string(),
+        match r.parse.result {
+            Ok(_) => duration(&r.parse.elapsed),
+            Err(_) => "FAILED".to_string(),
+        },
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    ];
+    for (part, p) in &r.parts {
+        let i = usize::from(*part - 1) * 2;
+        cells[2 + i] = answer(&p.result);
+        cells[3 + i] = duration(&p.elapsed);
+    }
+
+    cells
+}
+
+// Profile renders each phase of the runs as a row of time and allocations.
+pub struct Profile<'a>(pub &'a [Run]);
+
+const PROFILE_HEADERS: [&str; 6] = ["puzzle", "phase", "time", "allocs", "allocated", "peak"];
+
+impl fmt::Display for Profile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |puzzle: String, phase: String, elapsed: &Duration, alloc: &Stats| {
+            [
+                puzzle,
+                phase,
+                format!("{elapsed:.2?}"),
+                alloc.allocs.to_string(),
+                profile::bytes(alloc.bytes),
+                profile::bytes(alloc.peak),
+            ]
+        };
+
+        let mut rows = vec![];
+        for r in self.0 {
+            let (elapsed, alloc) = (&r.parse.elapsed, &r.parse.alloc);
+            rows.push(row(r.puzzle.to_string(), "parse".into(), elapsed, alloc));
+            for (part, t) in &r.parts {
+                rows.push(row(
+                    String::new(),
+                    format!("part {part}"),
+                    &t.elapsed,
+                    &t.alloc,
+                ));
+            }
+        }
+
+        columns(f, PROFILE_HEADERS, &rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn test_timed_catches_panics() {
+        let t = timed::<()>(|| panic!("oh no"));
+        assert_eq!("panicked: oh no", t.result.unwrap_err().to_string());
+
+        let t = timed(|| -> Result<()> { panic!("{} things", 2) });
+        assert_eq!("panicked: 2 things", t.result.unwrap_err().to_string());
+
+        assert_eq!(5, timed(|| Ok(5)).result.unwrap());
+    }
+
+    #[test]
+    fn test_run() {
+        let puzzle = solver::get(2022, 6).unwrap();
+
+        let r = run(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(0, r.errors().count());
+        assert_eq!(&Answer::Int(7), r.part(1).unwrap().result.as_ref().unwrap());
+        assert_eq!(
+            &Answer::Int(19),
+            r.part(2).unwrap().result.as_ref().unwrap()
+        );
+        assert!(r.part(3).is_none());
+
+        let r = run(solver::get(2022, 9).unwrap(), "X 1");
+        assert!(r.parse.result.is_err());
+        assert!(r.parts.is_empty());
+        assert_eq!(1, r.errors().count());
+    }
+
+    #[test]
+    fn test_run_parts() {
+        let puzzle = solver::get(2022, 6).unwrap();
+
+        let r = run_parts(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", Parts::Two);
+        assert!(r.part(1).is_none());
+        assert_eq!(
+            &Answer::Int(19),
+            r.part(2).unwrap().result.as_ref().unwrap()
+        );
+
+        let table = Table(&[r]).to_string();
+        let row = table.lines().nth(1).unwrap();
+        assert!(row.contains("  19  "));
+        assert!(!row.contains("  7  "));
+
+        assert_eq!(Parts::Both, "both".parse().unwrap());
+        assert_eq!(Parts::One, "1".parse().unwrap());
+        assert!("3".parse::<Parts>().is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let puzzle = solver::get(2022, 6).unwrap();
+        let r = run(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let json = serde_json::to_value(r.report(false)).unwrap();
+
+        assert_eq!(2022, json["year"]);
+        assert_eq!(6, json["day"]);
+        assert_eq!(true, json["ok"]);
+        assert!(json["parse"]["ms"].is_f64());
+        assert!(json["parse"]["error"].is_null());
+        assert_eq!(1, json["parts"][0]["part"]);
+        assert_eq!(7, json["parts"][0]["answer"]);
+        assert_eq!(19, json["parts"][1]["answer"]);
+
+        let r = run(solver::get(2022, 9).unwrap(), "X 1");
+        let json = serde_json::to_value(r.report(false)).unwrap();
+        assert_eq!(false, json["ok"]);
+        assert!(json["parse"]["error"].is_string());
+        assert_eq!(0, json["parts"].as_array().unwrap().len());
+    }
+
+    #[test]
+    fn test_profile() {
+        let puzzle = solver::get(2022, 6).unwrap();
+        let runs = [run(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb")];
+        let table = Profile(&runs).to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("puzzle      phase"));
+        assert!(lines[1].starts_with("2022 day 6  parse"));
+        assert!(lines[3].starts_with("            part 2"));
+
+        let json = serde_json::to_value(runs[0].report(true)).unwrap();
+        assert!(json["parts"][1]["alloc"]["allocs"].is_u64());
+        let json = serde_json::to_value(runs[0].report(false)).unwrap();
+        assert!(json["parts"][1].get("alloc").is_none());
+    }
+
+    #[test]
+    fn test_table() {
+        let puzzle = solver::get(2022, 6).unwrap();
+        let runs = [run(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb")];
+        let table = Table(&runs).to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(2, lines.len());
+        assert!(lines[0].starts_with("puzzle      parse"));
+        assert!(lines[1].starts_with("2022 day 6  "));
+        assert!(lines[1].contains("  7  "));
+        assert!(lines[1].contains("  19  "));
+    }
+}
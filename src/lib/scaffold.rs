@@ -0,0 +1,182 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fixtures;
+use crate::prelude::*;
+
+// DEFAULT_SRC is the library source directory, relative to the repository root.
+pub const DEFAULT_SRC: &str = "src/lib";
+
+const TEMPLATE: &str = include_str!("year_2023/template/mod.rs");
+const REGISTER_PLACEHOLDER: &str = "// register!(YEAR, DAY, Solution);";
+
+// FIXTURE is an ignored example fixture to be filled in from the puzzle
+const FIXTURE: &str = r#"ignore = "fill in the example and its answers, then remove this line"
+part1 = 0
+part2 = 0
+input = '''
+'''
+"#;
+
+// new_day creates year_YYYY/day_DD under src from the template, with an
+// example fixture to fill in under fixtures, and wires it into the year module.
+// The year module is created and added to lib.rs if it doesn't exist yet.
+// Returns every file created or modified.
+pub fn new_day(src: &Path, fixtures: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    ensure!(year >= 2015, "advent of code started in 2015, not {year}");
+    ensure!(
+        (1..=25).contains(&day),
+        "day must be within 1-25, not {day}"
+    );
+
+    let year_dir = src.join(format!("year_{year}"));
+    let day_dir = year_dir.join(format!("day_{day:02}"));
+    ensure!(!day_dir.exists(), "{} already exists", day_dir.display());
+
+    let mut changed = vec![];
+
+    let year_mod = year_dir.join("mod.rs");
+    if !year_mod.exists() {
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&year_mod, "")?;
+
+        let lib = src.join("lib.rs");
+        add_mod(&lib, &format!("year_{year}"))?;
+        changed.push(lib);
+    }
+    add_mod(&year_mod, &format!("day_{day:02}"))?;
+    changed.push(year_mod);
+
+    fs::create_dir_all(&day_dir)?;
+    let day_mod = day_dir.join("mod.rs");
+    fs::write(&day_mod, day_source(year, day))?;
+    changed.push(day_mod);
+
+    let fixture_dir = fixtures::dir(fixtures, year, day);
+    let fixture = fixture_dir.join("example.toml");
+    if !fixture.exists() {
+        fs::create_dir_all(&fixture_dir)?;
+        fs::write(&fixture, FIXTURE)?;
+        changed.push(fixture);
+    }
+
+    Ok(changed)
+}
+
+// day_source fills in the template for a year and day
+fn day_source(year: u32, day: u32) -> String {
+    TEMPLATE.replace(
+        REGISTER_PLACEHOLDER,
+        &format!("register!({year}, {day}, Solution);"),
+    )
+}
+
+// add_mod inserts `pub mod <name>;` into the module at path, keeping the
+// existing `pub mod` lines with the same prefix sorted.
+fn add_mod(path: &Path, name: &str) -> Result<()> {
+    let src = fs::read_to_string(path)
+        .with_context(|| format!("failed to read module {}", path.display()))?;
+
+    let line = format!("pub mod {name};");
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let siblings = format!("pub mod {prefix}");
+
+    let mut lines = src.lines().collect::<Vec<_>>();
+    let pos = match lines
+        .iter()
+        .position(|l| l.starts_with(&siblings) && *l > line.as_str())
+    {
+        Some(pos) => pos,
+        None => lines
+            .iter()
+            .rposition(|l| l.starts_with(&siblings))
+            .map_or(0, |p| p + 1),
+    };
+    lines.insert(pos, &line);
+
+    fs::write(path, lines.join("\n") + "\n")
+        .with_context(|| format!("failed to write module {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a throwaway copy of the parts of src/lib that new_day touches
+    fn fixture(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("year_2023")).unwrap();
+        fs::write(
+            dir.join("lib.rs"),
+            "pub mod coord;\npub mod year_2022;\npub mod year_2023;\n\npub mod prelude {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("year_2023/mod.rs"),
+            "pub mod day_01;\npub mod day_12;\n\nmod template;\n",
+        )
+        .unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn test_template_registers() {
+        assert!(TEMPLATE.contains(REGISTER_PLACEHOLDER));
+
+        let src = day_source(2024, 3);
+        assert!(src.contains("\nregister!(2024, 3, Solution);\n"));
+        assert!(!src.contains(REGISTER_PLACEHOLDER));
+    }
+
+    #[test]
+    fn test_fixture_parses() {
+        let f: fixtures::Fixture = FIXTURE.parse().unwrap();
+        assert!(f.ignore.is_some());
+        assert_eq!("", f.input);
+    }
+
+    #[test]
+    fn test_new_day_existing_year() {
+        let dir = fixture("existing");
+
+        new_day(&dir, &dir.join("fixtures"), 2023, 5).unwrap();
+        new_day(&dir, &dir.join("fixtures"), 2023, 13).unwrap();
+        assert_eq!(
+            "pub mod day_01;\npub mod day_05;\npub mod day_12;\npub mod day_13;\n\nmod template;\n",
+            fs::read_to_string(dir.join("year_2023/mod.rs")).unwrap()
+        );
+        assert_eq!(
+            day_source(2023, 5),
+            fs::read_to_string(dir.join("year_2023/day_05/mod.rs")).unwrap()
+        );
+        assert_eq!(
+            FIXTURE,
+            fs::read_to_string(dir.join("fixtures/2023/5/example.toml")).unwrap()
+        );
+
+        assert!(new_day(&dir, &dir.join("fixtures"), 2023, 5).is_err());
+        assert!(new_day(&dir, &dir.join("fixtures"), 2023, 26).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_new_day_new_year() {
+        let dir = fixture("new");
+
+        let changed = new_day(&dir, &dir.join("fixtures"), 2024, 1).unwrap();
+        assert_eq!(4, changed.len());
+        assert_eq!(
+            "pub mod coord;\npub mod year_2022;\npub mod year_2023;\npub mod year_2024;\n\npub mod prelude {}\n",
+            fs::read_to_string(dir.join("lib.rs")).unwrap()
+        );
+        assert_eq!(
+            "pub mod day_01;\n",
+            fs::read_to_string(dir.join("year_2024/mod.rs")).unwrap()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Reverse;
+use std::collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+// Graph is anything which can be searched: nodes, each with edges to the
+// nodes around it.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    // neighbors returns the nodes one edge away from node and what each edge costs
+    fn neighbors(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+
+    // heuristic estimates the cost from node to the nearest goal for astar. It
+    // must never overestimate, or astar may miss the cheapest path. The default
+    // turns astar into dijkstra.
+    fn heuristic(&self, _node: &Self::Node) -> u64 {
+        0
+    }
+}
+
+// FromFn is a graph with neighbors from a function, for searches which don't
+// need a type of their own
+pub struct FromFn<N, F> {
+    f: F,
+    node: PhantomData<fn() -> N>,
+}
+
+pub fn from_fn<N, F>(f: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    FromFn {
+        f,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn neighbors(&self, node: &N) -> Vec<(N, u64)> {
+        (self.f)(node)
+    }
+}
+
+// Path is a walk through a graph, from a start to where it ended, and the total
+// cost of its edges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+impl<N> Path<N> {
+    pub fn start(&self) -> &N {
+        self.nodes.first().unwrap()
+    }
+
+    pub fn end(&self) -> &N {
+        self.nodes.last().unwrap()
+    }
+
+    // steps is the number of edges walked, which is the cost if every edge costs 1
+    pub fn steps(&self) -> usize {
+        self.nodes.len() - 1
+    }
+}
+
+// Costs are the cheapest costs a search found to each node it reached, and
+// where it came from to get there
+#[derive(Debug, Clone)]
+pub struct Costs<N> {
+    costs: HashMap<N, u64>,
+    parents: HashMap<N, N>,
+}
+
+impl<N> Default for Costs<N> {
+    fn default() -> Self {
+        Self {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Costs<N> {
+    pub fn get(&self, node: &N) -> Option<u64> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&N, u64)> {
+        self.costs.iter().map(|(n, c)| (n, *c))
+    }
+
+    // furthest returns the reached node which cost the most to get to
+    pub fn furthest(&self) -> Option<(&N, u64)> {
+        self.iter().max_by_key(|(_, c)| *c)
+    }
+
+    // path_to reconstructs the cheapest path found to node, if it was reached
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        let cost = self.get(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some(parent) = self.parents.get(nodes.last().unwrap()) {
+            nodes.push(parent.clone());
+        }
+        nodes.reverse();
+
+        Some(Path { nodes, cost })
+    }
+}
+
+// bfs finds the path with the fewest steps from any of starts to a node which is_goal,
+// ignoring edge costs
+pub fn bfs<G: Graph>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let (costs, goal) = breadth_first(g, starts, is_goal);
+    costs.path_to(&goal?)
+}
+
+// bfs_all returns the fewest steps to every node reachable from starts
+pub fn bfs_all<G: Graph>(g: &G, starts: impl IntoIterator<Item = G::Node>) -> Costs<G::Node> {
+    breadth_first(g, starts, |_| false).0
+}
+
+// dijkstra finds the cheapest path from any of starts to a node which is_goal
+pub fn dijkstra<G: Graph>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let (costs, goal) = best_first(g, starts, is_goal, false);
+    costs.path_to(&goal?)
+}
+
+// dijkstra_all returns the cheapest cost to every node reachable from starts
+pub fn dijkstra_all<G: Graph>(g: &G, starts: impl IntoIterator<Item = G::Node>) -> Costs<G::Node> {
+    best_first(g, starts, |_| false, false).0
+}
+
+// astar is dijkstra guided toward the goal by the graph's heuristic
+pub fn astar<G: Graph>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<Path<G::Node>> {
+    let (costs, goal) = best_first(g, starts, is_goal, true);
+    costs.path_to(&goal?)
+}
+
+// all_pairs returns the cheapest cost between every pair of nodes, from and to
+// one of nodes, which has a path. Nodes in between can be anything in the graph.
+pub fn all_pairs<G: Graph>(g: &G, nodes: &[G::Node]) -> HashMap<(G::Node, G::Node), u64> {
+    let mut pairs = HashMap::new();
+    for from in nodes {
+        let costs = dijkstra_all(g, [from.clone()]);
+        for to in nodes {
+            if let Some(c) = costs.get(to) {
+                pairs.insert((from.clone(), to.clone()), c);
+            }
+        }
+    }
+
+    pairs
+}
+
+// breadth_first walks outwards from starts until it reaches a goal, which is
+// returned, or runs out of nodes
+fn breadth_first<G: Graph>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+) -> (Costs<G::Node>, Option<G::Node>) {
+    let mut costs = Costs::default();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if costs.costs.insert(s.clone(), 0).is_none() {
+            queue.push_back(s);
+        }
+    }
+
+    while let Some(n) = queue.pop_front() {
+        if is_goal(&n) {
+            return (costs, Some(n));
+        }
+
+        let steps = costs.costs[&n] + 1;
+        for (next, _) in g.neighbors(&n) {
+            if let Entry::Vacant(e) = costs.costs.entry(next.clone()) {
+                e.insert(steps);
+                costs.parents.insert(next.clone(), n.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (costs, None)
+}
+
+// best_first always expands the cheapest node found so far, plus the heuristic's
+// estimate of what's left if it's used, until it reaches a goal or runs out of
+// nodes.
+fn best_first<G: Graph>(
+    g: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: bool,
+) -> (Costs<G::Node>, Option<G::Node>) {
+    let h = |n: &G::Node| if heuristic { g.heuristic(n) } else { 0 };
+
+    let mut costs = Costs::default();
+    // the heap holds (estimate, cost, index) with nodes stored by index, so nodes
+    // don't need to be Ord
+    let mut heap = BinaryHeap::new();
+    let mut nodes = vec![];
+    for s in starts {
+        if costs.costs.insert(s.clone(), 0).is_none() {
+            heap.push(Reverse((h(&s), 0, nodes.len())));
+            nodes.push(s);
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        let n = nodes[i].clone();
+        // a cheaper way here was found after this was queued
+        if cost > costs.costs[&n] {
+            continue;
+        }
+        if is_goal(&n) {
+            return (costs, Some(n));
+        }
+
+        for (next, edge) in g.neighbors(&n) {
+            let next_cost = cost + edge;
+            match costs.costs.entry(next.clone()) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => {
+                    e.insert(next_cost);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(next_cost);
+                }
+            }
+
+            costs.parents.insert(next.clone(), n.clone());
+            heap.push(Reverse((next_cost + h(&next), next_cost, nodes.len())));
+            nodes.push(next);
+        }
+    }
+
+    (costs, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a -1-> b -1-> c -1-> d, with a shortcut a -5-> d and a dead end e
+    fn weighted() -> impl Graph<Node = char> {
+        from_fn(|n: &char| match n {
+            'a' => vec![('b', 1), ('d', 5)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        })
+    }
+
+    // an open 10x10 grid, with the heuristic as the distance to (9, 9)
+    struct Open;
+
+    impl Graph for Open {
+        type Node = (i64, i64);
+
+        fn neighbors(&self, &(x, y): &(i64, i64)) -> Vec<((i64, i64), u64)> {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..10).contains(x) && (0..10).contains(y))
+                .map(|n| (n, 1))
+                .collect()
+        }
+
+        fn heuristic(&self, &(x, y): &(i64, i64)) -> u64 {
+            x.abs_diff(9) + y.abs_diff(9)
+        }
+    }
+
+    #[test]
+    fn test_bfs_and_dijkstra() {
+        let g = weighted();
+
+        let p = bfs(&g, ['a'], |n| *n == 'd').unwrap();
+        assert_eq!(vec!['a', 'd'], p.nodes);
+        assert_eq!(1, p.cost);
+
+        let p = dijkstra(&g, ['a'], |n| *n == 'd').unwrap();
+        assert_eq!(vec!['a', 'b', 'c', 'd'], p.nodes);
+        assert_eq!(3, p.cost);
+        assert_eq!((&'a', &'d', 3), (p.start(), p.end(), p.steps()));
+
+        assert_eq!(None, dijkstra(&g, ['a'], |n| *n == 'e'));
+        assert_eq!(None, bfs(&g, ['d'], |n| *n == 'a'));
+        assert_eq!(vec!['b'], bfs(&g, ['b'], |n| *n == 'b').unwrap().nodes);
+    }
+
+    #[test]
+    fn test_astar() {
+        let expected = dijkstra(&Open, [(0, 0)], |n| *n == (9, 9)).unwrap();
+        let p = astar(&Open, [(0, 0)], |n| *n == (9, 9)).unwrap();
+        assert_eq!(18, p.cost);
+        assert_eq!(expected.cost, p.cost);
+        assert_eq!(19, p.nodes.len());
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let p = bfs(&Open, [(0, 0), (9, 0), (5, 5)], |n| *n == (9, 9)).unwrap();
+        assert_eq!((5, 5), *p.start());
+        assert_eq!(8, p.cost);
+
+        let costs = bfs_all(&Open, [(0, 0), (9, 9)]);
+        assert_eq!(100, costs.len());
+        assert_eq!(Some(9), costs.get(&(0, 9)));
+        assert_eq!(9, costs.furthest().unwrap().1);
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let g = weighted();
+        let pairs = all_pairs(&g, &['a', 'c', 'd', 'e']);
+
+        assert_eq!(Some(&2), pairs.get(&('a', 'c')));
+        assert_eq!(Some(&3), pairs.get(&('a', 'd')));
+        assert_eq!(Some(&0), pairs.get(&('e', 'e')));
+        assert_eq!(None, pairs.get(&('d', 'a')));
+        assert_eq!(None, pairs.get(&('a', 'e')));
+        assert_eq!(7, pairs.len());
+    }
+}
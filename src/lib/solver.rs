@@ -0,0 +1,179 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::answer::Answer;
+use crate::prelude::*;
+
+/// Solver is implemented by every day's solution. The input is parsed once
+/// and then handed to each part.
+pub trait Solver {
+    /// Input is the day's puzzle input after parsing.
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Parsed is a type-erased Solver::Input produced by Puzzle::parse. It can only
+/// be handed back to the Puzzle which created it.
+pub struct Parsed(Box<dyn Any>);
+
+/// Puzzle is a registered Solver for a year and day. Puzzles are collected at
+/// link time with `register!` so that nothing needs to list them by hand.
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+
+    parse: fn(&str) -> Result<Parsed>,
+    part1: fn(&Parsed) -> Result<Answer>,
+    part2: fn(&Parsed) -> Result<Answer>,
+}
+
+inventory::collect!(Puzzle);
+
+impl Puzzle {
+    pub const fn new<S: Solver>(year: u32, day: u32) -> Self {
+        Self {
+            year,
+            day,
+            parse: parse_erased::<S>,
+            part1: part1_erased::<S>,
+            part2: part2_erased::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed> {
+        (self.parse)(input).with_context(|| format!("parsing input for {self}"))
+    }
+
+    pub fn part1(&self, input: &Parsed) -> Result<Answer> {
+        (self.part1)(input).with_context(|| format!("solving part 1 of {self}"))
+    }
+
+    pub fn part2(&self, input: &Parsed) -> Result<Answer> {
+        (self.part2)(input).with_context(|| format!("solving part 2 of {self}"))
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+impl fmt::Debug for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Puzzle")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .finish()
+    }
+}
+
+fn parse_erased<S: Solver>(input: &str) -> Result<Parsed> {
+    Ok(Parsed(Box::new(S::parse(input)?)))
+}
+
+fn part1_erased<S: Solver>(input: &Parsed) -> Result<Answer> {
+    S::part1(downcast::<S>(input)?)
+}
+
+fn part2_erased<S: Solver>(input: &Parsed) -> Result<Answer> {
+    S::part2(downcast::<S>(input)?)
+}
+
+fn downcast<S: Solver>(input: &Parsed) -> Result<&S::Input> {
+    input
+        .0
+        .downcast_ref()
+        .ok_or_else(|| anyhow!("parsed input was produced by a different puzzle"))
+}
+
+/// register adds a Solver to the registry under a year and day.
+///
+/// ```ignore
+/// register!(2023, 1, Solution);
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($year:literal, $day:literal, $solver:ty) => {
+        $crate::solver::inventory::submit! {
+            $crate::solver::Puzzle::new::<$solver>($year, $day)
+        }
+    };
+}
+
+#[doc(hidden)]
+pub use inventory;
+
+/// puzzles returns every registered puzzle ordered by year and then day.
+pub fn puzzles() -> Vec<&'static Puzzle> {
+    let mut all = inventory::iter::<Puzzle>.into_iter().collect::<Vec<_>>();
+    all.sort_by_key(|p| (p.year, p.day));
+    all
+}
+
+/// years returns every year with at least one registered puzzle, ascending.
+pub fn years() -> Vec<u32> {
+    let mut years = puzzles().into_iter().map(|p| p.year).collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+/// get returns the puzzle registered for the year and day, if any.
+pub fn get(year: u32, day: u32) -> Option<&'static Puzzle> {
+    inventory::iter::<Puzzle>
+        .into_iter()
+        .find(|p| p.year == year && p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registry_unique() {
+        let mut seen = HashSet::new();
+        for p in puzzles() {
+            assert!(seen.insert((p.year, p.day)), "{p} is registered twice");
+        }
+    }
+
+    #[test]
+    fn test_registry_contents() {
+        assert_eq!(vec![2022, 2023], years());
+        assert!(get(2022, 5).is_some());
+        assert!(get(2023, 12).is_some());
+        assert!(get(2023, 25).is_none());
+        assert!(get(1999, 1).is_none());
+
+        let days = puzzles()
+            .into_iter()
+            .filter(|p| p.year == 2023)
+            .map(|p| p.day)
+            .collect::<Vec<_>>();
+        assert_eq!((1..=12).collect::<Vec<_>>(), days);
+    }
+
+    #[test]
+    fn test_parsed_wrong_puzzle() {
+        let day_06 = get(2022, 6).unwrap();
+        let day_09 = get(2022, 9).unwrap();
+
+        let parsed = day_06.parse("abcdefghijklmnop").unwrap();
+        assert!(day_09.part1(&parsed).is_err());
+    }
+
+    #[test]
+    fn test_puzzle_answers() {
+        let day_06 = get(2022, 6).unwrap();
+
+        let parsed = day_06.parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert_eq!(Answer::Int(7), day_06.part1(&parsed).unwrap());
+        assert_eq!(Answer::Int(19), day_06.part2(&parsed).unwrap());
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{
+    coord::{Coordinate, Orientation},
+    grid::Grid,
+};
+
+// SparseGrid is an unbounded grid which only stores the tiles which are set,
+// keyed by signed coordinates. It keeps track of the bounds of its tiles, which
+// is the extent it displays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    data: HashMap<Coordinate<i64>, T>,
+    // the min and max corners of every tile, inclusive
+    bounds: Option<(Coordinate<i64>, Coordinate<i64>)>,
+    // shown for tiles which aren't set
+    glyph: char,
+    orientation: Orientation,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            data: HashMap::new(),
+            bounds: None,
+            glyph: '.',
+            orientation: Orientation::YDown,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    // new returns an empty grid which displays unset tiles as '.', with y
+    // increasing down like Grid
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // with_glyph sets what's displayed for tiles which aren't set
+    pub fn with_glyph(mut self, glyph: char) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    // with_orientation sets which way y increases when displayed
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, c: &Coordinate<i64>) -> Option<&T> {
+        self.data.get(c)
+    }
+
+    pub fn get_mut(&mut self, c: &Coordinate<i64>) -> Option<&mut T> {
+        self.data.get_mut(c)
+    }
+
+    pub fn contains(&self, c: &Coordinate<i64>) -> bool {
+        self.data.contains_key(c)
+    }
+
+    // insert sets the tile at c, returning what was there
+    pub fn insert(&mut self, c: Coordinate<i64>, t: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (c, c),
+            Some((min, max)) => (
+                Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+                Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+            ),
+        });
+
+        self.data.insert(c, t)
+    }
+
+    // remove unsets the tile at c, shrinking the bounds if it was on an edge
+    pub fn remove(&mut self, c: &Coordinate<i64>) -> Option<T> {
+        let t = self.data.remove(c)?;
+        if let Some((min, max)) = self.bounds {
+            if c.x == min.x || c.y == min.y || c.x == max.x || c.y == max.y {
+                self.bounds = self.data.keys().fold(None, |b, c| match b {
+                    None => Some((*c, *c)),
+                    Some((min, max)) => Some((
+                        Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+                        Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+                    )),
+                });
+            }
+        }
+
+        Some(t)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Coordinate<i64>, &T)> {
+        self.data.iter()
+    }
+
+    // bounds returns the min and max corners of the set tiles, inclusive
+    pub fn bounds(&self) -> Option<(Coordinate<i64>, Coordinate<i64>)> {
+        self.bounds
+    }
+
+    // dimensions returns the width and height of the bounds
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.bounds.map_or((0, 0), |(min, max)| {
+            ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize)
+        })
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // to_grid returns a dense grid covering the bounds, with empty where tiles
+    // aren't set, and the coordinate its (0, 0) is at. Rows stay in the same
+    // order, so y increases down the grid.
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Coordinate<i64>) {
+        let Some((min, _)) = self.bounds else {
+            return (Grid::filled(0, 0, empty), Coordinate::new(0, 0));
+        };
+
+        let (width, height) = self.dimensions();
+        let mut grid = Grid::filled(width, height, empty);
+        for (c, t) in &self.data {
+            grid[((c.x - min.x) as usize, (c.y - min.y) as usize)] = t.clone();
+        }
+
+        (grid, min)
+    }
+
+    // from_grid sets the tiles of grid which keep says to, with the grid's (0, 0)
+    // at origin
+    pub fn from_grid(
+        grid: &Grid<T>,
+        origin: Coordinate<i64>,
+        mut keep: impl FnMut(&T) -> bool,
+    ) -> Self {
+        let mut sparse = Self::new();
+        for ((x, y), t) in grid.iter().filter(|(_, t)| keep(t)) {
+            sparse.insert(origin + Coordinate::new(x as i64, y as i64), t.clone());
+        }
+
+        sparse
+    }
+}
+
+impl<T> FromIterator<(Coordinate<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coordinate<i64>, T)>>(iter: I) -> Self {
+        let mut sparse = Self::new();
+        for (c, t) in iter {
+            sparse.insert(c, t);
+        }
+
+        sparse
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+
+        let rows: Box<dyn Iterator<Item = i64>> = match self.orientation {
+            Orientation::YDown => Box::new(min.y..=max.y),
+            Orientation::YUp => Box::new((min.y..=max.y).rev()),
+        };
+        for y in rows {
+            for x in min.x..=max.x {
+                match self.data.get(&Coordinate::new(x, y)) {
+                    Some(t) => write!(f, "{t}")?,
+                    None => write!(f, "{}", self.glyph)?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut g = SparseGrid::new();
+        assert_eq!(None, g.bounds());
+        assert_eq!((0, 0), g.dimensions());
+
+        g.insert(Coordinate::new(2, 3), 'a');
+        assert_eq!(
+            Some((Coordinate::new(2, 3), Coordinate::new(2, 3))),
+            g.bounds()
+        );
+
+        g.insert(Coordinate::new(-1, 5), 'b');
+        g.insert(Coordinate::new(0, 4), 'c');
+        assert_eq!(
+            Some((Coordinate::new(-1, 3), Coordinate::new(2, 5))),
+            g.bounds()
+        );
+        assert_eq!((4, 3), g.dimensions());
+        assert_eq!(Some('c'), g.insert(Coordinate::new(0, 4), 'd'));
+        assert_eq!(3, g.len());
+
+        // removing from the middle doesn't shrink, but an edge does
+        assert_eq!(Some('d'), g.remove(&Coordinate::new(0, 4)));
+        assert_eq!((4, 3), g.dimensions());
+        assert_eq!(Some('b'), g.remove(&Coordinate::new(-1, 5)));
+        assert_eq!(
+            Some((Coordinate::new(2, 3), Coordinate::new(2, 3))),
+            g.bounds()
+        );
+        assert_eq!(None, g.remove(&Coordinate::new(-1, 5)));
+    }
+
+    #[test]
+    fn test_display() {
+        let g = [((0, 0), 'S'), ((-2, 1), '#'), ((1, 2), 'E')]
+            .into_iter()
+            .map(|(c, t)| (Coordinate::from(c), t))
+            .collect::<SparseGrid<_>>();
+
+        assert_eq!("..S.\n#...\n...E\n", g.to_string());
+        let g = g.with_glyph(' ').with_orientation(Orientation::YUp);
+        assert_eq!("   E\n#   \n  S \n", g.to_string());
+        assert_eq!("", SparseGrid::<char>::new().to_string());
+    }
+
+    #[test]
+    fn test_dense() {
+        let grid = Grid::parse("#..\n.#.\n..#", |c| Ok(c == '#')).unwrap();
+        let sparse = SparseGrid::from_grid(&grid, Coordinate::new(-5, 10), |t| *t);
+        assert_eq!(3, sparse.len());
+        assert!(sparse.contains(&Coordinate::new(-4, 11)));
+
+        let (dense, origin) = sparse.to_grid(false);
+        assert_eq!(Coordinate::new(-5, 10), origin);
+        assert_eq!(grid, dense);
+
+        let (dense, _) = SparseGrid::<bool>::new().to_grid(false);
+        assert_eq!((0, 0), dense.dimensions());
+    }
+}
@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use num::BigInt;
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, Response, Verdict};
+use crate::prelude::*;
+
+// DEFAULT_HISTORY is where submitted guesses are recorded, relative to the
+// repository root.
+pub const DEFAULT_HISTORY: &str = "guesses.toml";
+
+// Guess is a submitted answer and how it was judged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    // unix seconds when the guess was submitted
+    pub at: u64,
+    // unix seconds until another answer may be submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+// History is every guess we've submitted, stored as TOML.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    // load reads the history at path. A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let guesses = match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str::<HistoryFile>(&s)
+                    .with_context(|| format!("failed to parse guesses in {}", path.display()))?
+                    .guesses
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let file = HistoryFile {
+            guesses: self.guesses.clone(),
+        };
+        fs::write(&self.path, toml::to_string(&file)?)
+            .with_context(|| format!("failed to write guesses to {}", self.path.display()))
+    }
+
+    // guesses returns every guess for a part, oldest first
+    pub fn guesses(&self, year: u32, day: u32, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| g.year == year && g.day == day && g.part == part)
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    // check returns an error explaining why an answer shouldn't be submitted:
+    // the part is solved, the answer was already wrong or is outside the bounds
+    // that earlier guesses established, or the site asked us to wait.
+    pub fn check(&self, year: u32, day: u32, part: u8, answer: &str, now: u64) -> Result<()> {
+        let value = answer.parse::<BigInt>().ok();
+
+        for g in self.guesses(year, day, part) {
+            match g.verdict {
+                Verdict::Correct if g.answer == answer => bail!("{answer} was already accepted"),
+                Verdict::Correct => bail!("already solved with {}", g.answer),
+                Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow if g.answer == answer => {
+                    bail!("{answer} was already rejected")
+                }
+                _ => (),
+            }
+
+            let bound = g.answer.parse::<BigInt>().ok();
+            match (g.verdict, &value, bound) {
+                (Verdict::TooHigh, Some(v), Some(b)) if *v >= b => {
+                    bail!("{answer} is too high, {} already was", g.answer)
+                }
+                (Verdict::TooLow, Some(v), Some(b)) if *v <= b => {
+                    bail!("{answer} is too low, {} already was", g.answer)
+                }
+                _ => (),
+            }
+        }
+
+        // any part of any day counts towards the site's wait
+        if let Some(until) = self.guesses.iter().filter_map(|g| g.wait_until).max() {
+            ensure!(
+                now >= until,
+                "the site asked us to wait another {}s before submitting",
+                until - now
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// submit sends an answer for a part unless history says it's pointless, then
+// records and saves the result.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: &Answer,
+) -> Result<Response> {
+    ensure!(part == 1 || part == 2, "part must be 1 or 2, not {part}");
+    ensure!(
+        !answer.is_multiline(),
+        "multi-line answers have to be read and submitted by hand:\n{answer}"
+    );
+
+    let answer = answer.to_string();
+    let at = now();
+    history.check(year, day, part, &answer, at)?;
+
+    let resp = client.submit(year, day, part, &answer)?;
+    history.record(Guess {
+        year,
+        day,
+        part,
+        answer,
+        verdict: resp.verdict,
+        at,
+        wait_until: resp.wait.map(|w| at + w.as_secs()),
+    });
+    history.save()?;
+
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{test_server::TestServer, Http};
+
+    fn guess(part: u8, answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            year: 2023,
+            day: 9,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            at: 100,
+            wait_until: None,
+        }
+    }
+
+    fn history(guesses: Vec<Guess>) -> History {
+        History {
+            path: PathBuf::new(),
+            guesses,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let h = history(vec![
+            guess(1, "500", Verdict::TooHigh),
+            guess(1, "100", Verdict::TooLow),
+            guess(1, "250", Verdict::Wrong),
+            guess(2, "42", Verdict::Correct),
+        ]);
+
+        assert!(h.check(2023, 9, 1, "300", 200).is_ok());
+        assert!(h.check(2023, 9, 1, "250", 200).is_err());
+        assert!(h.check(2023, 9, 1, "500", 200).is_err());
+        assert!(h.check(2023, 9, 1, "501", 200).is_err());
+        assert!(h.check(2023, 9, 1, "100", 200).is_err());
+        assert!(h.check(2023, 9, 1, "-3", 200).is_err());
+        assert!(h.check(2023, 9, 1, "abc", 200).is_ok());
+        assert!(h.check(2023, 9, 2, "42", 200).is_err());
+        assert!(h.check(2023, 9, 2, "43", 200).is_err());
+        assert!(h.check(2023, 10, 1, "501", 200).is_ok());
+    }
+
+    #[test]
+    fn test_check_wait() {
+        let mut g = guess(1, "500", Verdict::TooHigh);
+        g.wait_until = Some(160);
+        let h = history(vec![g]);
+
+        assert!(h.check(2023, 1, 1, "1", 159).is_err());
+        assert!(h.check(2023, 1, 1, "1", 160).is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        let path = std::env::temp_dir().join(format!("aoc-guesses-{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let server = TestServer::start(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>",
+        )]);
+        let client = Client::new(Http::with_base(&server.base, "abc123"));
+
+        let mut h = History::load(&path).unwrap();
+        let resp = submit(&client, &mut h, 2023, 9, 1, &Answer::Int(100)).unwrap();
+        assert_eq!(Verdict::TooLow, resp.verdict);
+
+        // reloaded history refuses the same answer without contacting the site
+        let mut h = History::load(&path).unwrap();
+        let g = h.guesses(2023, 9, 1).next().unwrap().clone();
+        assert_eq!("100", g.answer);
+        assert_eq!(Some(g.at + 60), g.wait_until);
+        assert!(submit(&client, &mut h, 2023, 9, 1, &Answer::Int(100)).is_err());
+        assert!(submit(&client, &mut h, 2023, 9, 1, &Answer::Int(90)).is_err());
+        assert_eq!(1, server.requests().len());
+
+        let screen = Answer::Lines(vec!["#.".to_string(), ".#".to_string()]);
+        assert!(submit(&client, &mut h, 2022, 10, 2, &screen).is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::inputs;
+use crate::prelude::*;
+use crate::runner;
+use crate::solver::{self, Puzzle};
+
+// DEFAULT_ANSWERS is where accepted answers are stored, relative to the repository root.
+pub const DEFAULT_ANSWERS: &str = "answers.toml";
+
+// Answers are the accepted answers for our inputs, keyed by year then day:
+//
+//   [2023.9]
+//   part1 = 2008960228
+//   part2 = 1097
+//
+// Answers which don't fit in a TOML integer or span multiple lines are stored
+// as strings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u32, u32), [Option<Answer>; 2]>);
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayEntry {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let raw: BTreeMap<String, BTreeMap<String, DayEntry>> = toml::from_str(s)?;
+        let mut answers = BTreeMap::new();
+
+        for (year, days) in raw {
+            let year = year
+                .parse()
+                .with_context(|| format!("invalid year '{year}'"))?;
+            for (day, entry) in days {
+                let day = day
+                    .parse()
+                    .with_context(|| format!("invalid day '{day}' in {year}"))?;
+                let part1 = entry.part1.map(expected_answer).transpose()?;
+                let part2 = entry.part2.map(expected_answer).transpose()?;
+                answers.insert((year, day), [part1, part2]);
+            }
+        }
+
+        Ok(Self(answers))
+    }
+}
+
+pub(crate) fn expected_answer(v: toml::Value) -> Result<Answer> {
+    match v {
+        toml::Value::Integer(i) => Ok(i.into()),
+        toml::Value::String(s) => s.parse(),
+        other => bail!("unsupported answer type {}: {other}", other.type_str()),
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        read_to_string(path)
+            .with_context(|| format!("failed to read answers from {}", path.display()))?
+            .parse()
+            .with_context(|| format!("failed to parse answers in {}", path.display()))
+    }
+
+    // get returns the accepted answer for a part, 1 or 2, if recorded
+    pub fn get(&self, year: u32, day: u32, part: u8) -> Option<&Answer> {
+        self.0
+            .get(&(year, day))
+            .and_then(|parts| parts.get(usize::from(part).checked_sub(1)?))
+            .and_then(Option::as_ref)
+    }
+}
+
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    // the solver ran, but there's no accepted answer to check it against
+    MissingAnswer(Answer),
+    MissingInput,
+    Error(Error),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual }
+                if expected.is_multiline() || actual.is_multiline() =>
+            {
+                write!(f, "FAIL: expected:\n{expected}\ngot:\n{actual}")
+            }
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL: expected {expected}, got {actual}")
+            }
+            Status::MissingAnswer(actual) if actual.is_multiline() => {
+                write!(f, "missing answer, got:\n{actual}")
+            }
+            Status::MissingAnswer(actual) => write!(f, "missing answer, got {actual}"),
+            Status::MissingInput => write!(f, "missing input"),
+            Status::Error(e) => write!(f, "ERROR: {e:#}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub puzzle: &'static Puzzle,
+    pub part: u8,
+    pub status: Status,
+}
+
+impl fmt::Display for PartReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} part {}: {}", self.puzzle, self.part, self.status)
+    }
+}
+
+// verify runs a puzzle against its input and checks both parts against the
+// accepted answers.
+pub fn verify(puzzle: &'static Puzzle, input: &str, answers: &Answers) -> Vec<PartReport> {
+    let run = runner::run(puzzle, input);
+    let results = match run.parse.result {
+        Err(e) => vec![Err(anyhow!("{e:#}")), Err(e)],
+        Ok(()) => run.parts.into_iter().map(|(_, p)| p.result).collect(),
+    };
+
+    (1..=2)
+        .zip(results)
+        .map(|(part, actual)| {
+            let status = match (actual, answers.get(puzzle.year, puzzle.day, part)) {
+                (Err(e), _) => Status::Error(e),
+                (Ok(actual), None) => Status::MissingAnswer(actual),
+                (Ok(actual), Some(expected)) if actual == *expected => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail {
+                    expected: expected.clone(),
+                    actual,
+                },
+            };
+
+            PartReport {
+                puzzle,
+                part,
+                status,
+            }
+        })
+        .collect()
+}
+
+// verify_all verifies every registered puzzle, optionally only for one year,
+// reading inputs from the input directory.
+pub fn verify_all(inputs_dir: &Path, answers: &Answers, year: Option<u32>) -> Vec<PartReport> {
+    solver::puzzles()
+        .into_iter()
+        .filter(|p| year.map_or(true, |y| p.year == y))
+        .flat_map(|puzzle| {
+            match read_to_string(inputs::path(inputs_dir, puzzle.year, puzzle.day)) {
+                Ok(input) => verify(puzzle, &input, answers),
+                Err(_) => (1..=2)
+                    .map(|part| PartReport {
+                        puzzle,
+                        part,
+                        status: Status::MissingInput,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[2022.6]
+part1 = 7
+part2 = 20
+
+[2022.10]
+part2 = """
+##..
+..##
+"""
+
+[2023.1]
+part1 = "123456789012345678901234567890123456789012345"
+"#;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+
+        assert_eq!(Some(&Answer::Int(7)), answers.get(2022, 6, 1));
+        assert_eq!(Some(&Answer::Int(20)), answers.get(2022, 6, 2));
+        assert_eq!(None, answers.get(2022, 10, 1));
+        assert_eq!(
+            Some(&Answer::Lines(vec!["##..".to_string(), "..##".to_string()])),
+            answers.get(2022, 10, 2)
+        );
+        assert!(matches!(answers.get(2023, 1, 1), Some(Answer::BigInt(_))));
+        assert_eq!(None, answers.get(2023, 2, 1));
+        assert_eq!(None, answers.get(2022, 6, 3));
+
+        assert!("[2023.x]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[2023.1]\npart3 = 1".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        let puzzle = solver::get(2022, 6).unwrap();
+
+        let report = verify(puzzle, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", &answers);
+        assert!(matches!(report[0].status, Status::Pass));
+        assert!(matches!(
+            report[1].status,
+            Status::Fail {
+                expected: Answer::Int(20),
+                actual: Answer::Int(19)
+            }
+        ));
+
+        let puzzle = solver::get(2022, 9).unwrap();
+        let report = verify(puzzle, "R 4\nU 4", &answers);
+        assert!(matches!(report[0].status, Status::MissingAnswer(_)));
+
+        let report = verify(puzzle, "not an order", &answers);
+        assert!(report.iter().all(|r| r.status.is_failure()));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::fixtures::{self, Fixture};
+use crate::prelude::*;
+use crate::runner::{self, Parts};
+use crate::solver::Puzzle;
+
+// POLL_INTERVAL is how often watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Watcher reruns a puzzle against an input and the day's fixtures whenever any
+// of them change, reporting how the answers changed since the last run.
+pub struct Watcher {
+    puzzle: &'static Puzzle,
+    parts: Parts,
+    input: PathBuf,
+    fixtures: PathBuf,
+    // the contents of every watched file as of the last run, input first
+    seen: Vec<(PathBuf, Option<String>)>,
+    // the answers from the last run for each watched file
+    answers: BTreeMap<PathBuf, Vec<(u8, String)>>,
+}
+
+impl Watcher {
+    pub fn new(puzzle: &'static Puzzle, parts: Parts, input: &Path, fixtures_dir: &Path) -> Self {
+        Self {
+            puzzle,
+            parts,
+            input: input.to_path_buf(),
+            fixtures: fixtures::dir(fixtures_dir, puzzle.year, puzzle.day),
+            seen: vec![],
+            answers: BTreeMap::new(),
+        }
+    }
+
+    // paths returns the input and every fixture for the day, which are found
+    // again on each poll so that new fixtures are picked up
+    fn paths(&self) -> Vec<PathBuf> {
+        let mut fixtures = fs::read_dir(&self.fixtures)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.extension().map_or(false, |e| e == "toml"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        fixtures.sort();
+
+        std::iter::once(self.input.clone())
+            .chain(fixtures)
+            .collect()
+    }
+
+    // poll reruns the puzzle if anything watched changed since the last poll,
+    // and always on the first, returning a report of the answers
+    pub fn poll(&mut self) -> Option<String> {
+        let seen = self
+            .paths()
+            .into_iter()
+            .map(|p| {
+                let contents = fs::read_to_string(&p).ok();
+                (p, contents)
+            })
+            .collect::<Vec<_>>();
+        if seen == self.seen {
+            return None;
+        }
+        self.seen = seen;
+
+        let mut report = String::new();
+        let mut answers = BTreeMap::new();
+        for (path, contents) in &self.seen {
+            let _ = writeln!(report, "{}", path.display());
+
+            let (input, expected) = match self.source(path, contents.as_deref()) {
+                Ok(source) => source,
+                Err(e) => {
+                    let _ = writeln!(report, "  {e:#}");
+                    continue;
+                }
+            };
+
+            let run = runner::run_parts(self.puzzle, &input, self.parts);
+            if let Err(e) = &run.parse.result {
+                let _ = writeln!(report, "  parse failed: {e:#}");
+            }
+
+            let previous = self.answers.get(path);
+            let mut current = vec![];
+            for (part, t) in &run.parts {
+                let answer = match &t.result {
+                    Ok(a) => a.to_string(),
+                    Err(e) => format!("error: {e:#}"),
+                };
+
+                let was = previous
+                    .and_then(|p| p.iter().find(|(n, _)| n == part))
+                    .map(|(_, a)| a)
+                    .filter(|a| **a != answer);
+                let _ = write!(report, "  {}", line(*part, &answer, t.elapsed));
+                if let Some(was) = was {
+                    let _ = write!(report, " (was {was})");
+                }
+                if let Some(expected) = &expected[usize::from(*part - 1)] {
+                    if t.result.as_ref().ok() != Some(expected) {
+                        let _ = write!(report, " FAIL, expected {expected}");
+                    }
+                }
+                report.push('\n');
+
+                current.push((*part, answer));
+            }
+            answers.insert(path.clone(), current);
+        }
+
+        self.answers = answers;
+        Some(report)
+    }
+
+    // source returns the input in a watched file and any answers it expects
+    fn source(&self, path: &Path, contents: Option<&str>) -> Result<(String, [Option<Answer>; 2])> {
+        let contents = contents.ok_or_else(|| anyhow!("can't be read"))?;
+        if *path == self.input {
+            return Ok((contents.to_string(), [None, None]));
+        }
+
+        let fixture: Fixture = contents.parse().context("invalid fixture")?;
+        Ok((fixture.input, fixture.answers))
+    }
+}
+
+// multi-line answers start on their own line so they stay aligned
+fn line(part: u8, answer: &str, elapsed: Duration) -> String {
+    if answer.contains('\n') {
+        format!("part {part} ({elapsed:.2?}):\n{answer}\n ")
+    } else {
+        format!("part {part}: {answer} ({elapsed:.2?})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn test_poll() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let input = dir.join("6.txt");
+        let fixtures = dir.join("fixtures");
+        fs::create_dir_all(fixtures.join("2022/6")).unwrap();
+        fs::write(&input, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n").unwrap();
+
+        let puzzle = solver::get(2022, 6).unwrap();
+        let mut w = Watcher::new(puzzle, Parts::Both, &input, &fixtures);
+
+        let report = w.poll().unwrap();
+        assert!(report.contains("part 1: 7 ("));
+        assert!(report.contains("part 2: 19 ("));
+        assert!(w.poll().is_none());
+
+        fs::write(&input, "bvwbjplbgvbhsrlpgdmjqwftvncz\n").unwrap();
+        let report = w.poll().unwrap();
+        assert!(report.contains(") (was 7)"));
+        assert!(report.contains(") (was 19)"));
+
+        fs::write(
+            fixtures.join("2022/6/example.toml"),
+            "part1 = 7\npart2 = 20\ninput = 'mjqjpqmgbljsphdztnvjfqwrcgsmlb'",
+        )
+        .unwrap();
+        let report = w.poll().unwrap();
+        assert!(report.contains("example.toml\n  part 1: 7 ("));
+        assert!(report.contains(" FAIL, expected 20"));
+        assert!(!report.contains("was"));
+
+        fs::write(fixtures.join("2022/6/example.toml"), "part1 =").unwrap();
+        assert!(w.poll().unwrap().contains("invalid fixture"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
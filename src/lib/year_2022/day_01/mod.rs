@@ -0,0 +1,59 @@
+use crate::prelude::*;
+
+pub struct Solution;
+
+impl Solver for Solution {
+    // the calories carried by each elf, summed
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        // elves are separated by a blank line, like the elisp's "\n\n" split
+        input
+            .trim()
+            .split("\n\n")
+            .map(|elf| {
+                elf.lines()
+                    .map(|l| l.trim().parse::<u64>().context("invalid calories"))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn part1(elves: &Self::Input) -> Result<Answer> {
+        Ok(top(elves, 1).into())
+    }
+
+    fn part2(elves: &Self::Input) -> Result<Answer> {
+        Ok(top(elves, 3).into())
+    }
+}
+
+register!(2022, 1, Solution);
+
+// top returns the total calories carried by the n elves carrying the most
+fn top(elves: &[u64], n: usize) -> u64 {
+    let mut elves = elves.to_vec();
+    elves.sort_unstable_by(|a, b| b.cmp(a));
+    elves.iter().take(n).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let elves = Solution::parse("1000\n2000\n\n4000\n\n5000\n6000\n").unwrap();
+        assert_eq!(vec![3000, 4000, 11000], elves);
+
+        assert!(Solution::parse("1000\nabc\n").is_err());
+    }
+
+    #[test]
+    fn test_top() {
+        assert_eq!(11000, top(&[3000, 4000, 11000], 1));
+        assert_eq!(18000, top(&[3000, 4000, 11000], 3));
+        // fewer elves than asked for
+        assert_eq!(7000, top(&[3000, 4000], 3));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::prelude::*;
+
+pub struct Solution;
+
+impl Solver for Solution {
+    // each round as (their shape, our column), both 0-2
+    type Input = Vec<(u8, u8)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| match l.trim().as_bytes() {
+                [them @ b'A'..=b'C', b' ', us @ b'X'..=b'Z'] => Ok((them - b'A', us - b'X')),
+                _ => bail!("invalid round '{l}'"),
+            })
+            .collect()
+    }
+
+    // our column is the shape we play
+    fn part1(rounds: &Self::Input) -> Result<Answer> {
+        Ok(rounds
+            .iter()
+            .map(|&(them, us)| score(them, us))
+            .sum::<u32>()
+            .into())
+    }
+
+    // our column is the outcome we need: X = lose, Y = draw, Z = win
+    fn part2(rounds: &Self::Input) -> Result<Answer> {
+        Ok(rounds
+            .iter()
+            .map(|&(them, outcome)| score(them, (them + outcome + 2) % 3))
+            .sum::<u32>()
+            .into())
+    }
+}
+
+register!(2022, 2, Solution);
+
+// score returns our points for a round. Shapes are 0 = rock, 1 = paper and
+// 2 = scissors, each beating the one before it. Playing a shape is worth one
+// more than its number, and a loss, draw or win are worth 0, 3 or 6.
+fn score(them: u8, us: u8) -> u32 {
+    let outcome = (us + 4 - them) % 3;
+    u32::from(us) + 1 + u32::from(outcome) * 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        // the elisp solution's score table
+        let table = [
+            ("A X", 4),
+            ("B X", 1),
+            ("C X", 7),
+            ("A Y", 8),
+            ("B Y", 5),
+            ("C Y", 2),
+            ("A Z", 3),
+            ("B Z", 9),
+            ("C Z", 6),
+        ];
+
+        for (round, expected) in table {
+            let (them, us) = Solution::parse(round).unwrap()[0];
+            assert_eq!(expected, score(them, us), "{round}");
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(Solution::parse("A X\nD Y\n").is_err());
+        assert!(Solution::parse("AX\n").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+pub struct Solution;
+
+impl Solver for Solution {
+    // each rucksack's items as a set of priorities
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Result<Answer> {
+        rucksacks
+            .iter()
+            .map(|r| only(r.left & r.right))
+            .sum::<Result<u32>>()
+            .map(Into::into)
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Result<Answer> {
+        ensure!(
+            rucksacks.len() % 3 == 0,
+            "{} rucksacks can't be split into groups of three",
+            rucksacks.len()
+        );
+
+        rucksacks
+            .chunks(3)
+            .map(|g| only(g.iter().fold(u64::MAX, |acc, r| acc & r.items())))
+            .sum::<Result<u32>>()
+            .map(Into::into)
+    }
+}
+
+register!(2022, 3, Solution);
+
+// Rucksack is the items in each compartment as bitsets indexed by priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    left: u64,
+    right: u64,
+}
+
+impl Rucksack {
+    fn items(&self) -> u64 {
+        self.left | self.right
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ensure!(s.len() % 2 == 0, "'{s}' can't be split in half");
+
+        let set = |half: &str| {
+            half.bytes()
+                .try_fold(0u64, |acc, c| Ok::<_, Error>(acc | 1 << priority(c)?))
+        };
+        let (left, right) = s.split_at(s.len() / 2);
+
+        Ok(Self {
+            left: set(left)?,
+            right: set(right)?,
+        })
+    }
+}
+
+// priority returns a-z as 1-26 and A-Z as 27-52, like the elisp's score-char
+fn priority(c: u8) -> Result<u32> {
+    match c {
+        b'a'..=b'z' => Ok(u32::from(c - b'a') + 1),
+        b'A'..=b'Z' => Ok(u32::from(c - b'A') + 27),
+        _ => bail!("invalid item '{}'", c as char),
+    }
+}
+
+// only returns the priority of the single item in a set
+fn only(set: u64) -> Result<u32> {
+    ensure!(
+        set.count_ones() == 1,
+        "expected one item in common, found {}",
+        set.count_ones()
+    );
+    Ok(set.trailing_zeros())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(1, priority(b'a').unwrap());
+        assert_eq!(26, priority(b'z').unwrap());
+        assert_eq!(27, priority(b'A').unwrap());
+        assert_eq!(52, priority(b'Z').unwrap());
+        assert!(priority(b'1').is_err());
+    }
+
+    #[test]
+    fn test_common_item() {
+        let r: Rucksack = "vJrwpWtwJgWrhcsFMMfFFhFp".parse().unwrap();
+        // p
+        assert_eq!(16, only(r.left & r.right).unwrap());
+
+        assert!("abc".parse::<Rucksack>().is_err());
+        let r: Rucksack = "abab".parse().unwrap();
+        assert!(only(r.left & r.right).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use std::ops::RangeInclusive;
+
+use crate::prelude::*;
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let (a, b) = l
+                    .split_once(',')
+                    .ok_or_else(|| anyhow!("expected two ranges in '{l}'"))?;
+                Ok((range(a)?, range(b)?))
+            })
+            .collect()
+    }
+
+    fn part1(pairs: &Self::Input) -> Result<Answer> {
+        Ok(pairs.iter().filter(|(a, b)| contains(a, b)).count().into())
+    }
+
+    fn part2(pairs: &Self::Input) -> Result<Answer> {
+        Ok(pairs.iter().filter(|(a, b)| overlaps(a, b)).count().into())
+    }
+}
+
+register!(2022, 4, Solution);
+
+// range parses A-B
+fn range(s: &str) -> Result<RangeInclusive<u32>> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| anyhow!("invalid range '{s}'"))?;
+    Ok(start.parse()?..=end.parse()?)
+}
+
+// contains returns whether either range contains the other. Equal ranges count.
+fn contains(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> bool {
+    (a.start() <= b.start() && a.end() >= b.end()) || (b.start() <= a.start() && b.end() >= a.end())
+}
+
+fn overlaps(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> bool {
+    a.start() <= b.end() && b.start() <= a.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        assert!(contains(&(2..=8), &(3..=7)));
+        assert!(contains(&(6..=6), &(4..=6)));
+        assert!(contains(&(3..=3), &(3..=3)));
+        assert!(!contains(&(5..=7), &(7..=9)));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(overlaps(&(5..=7), &(7..=9)));
+        assert!(overlaps(&(2..=8), &(3..=7)));
+        assert!(!overlaps(&(2..=4), &(6..=8)));
+        assert!(!overlaps(&(6..=8), &(2..=4)));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(vec![(2..=4, 6..=8)], Solution::parse("2-4,6-8\n").unwrap());
+        assert!(Solution::parse("2-4\n").is_err());
+        assert!(Solution::parse("2-a,6-8\n").is_err());
+    }
+}
@@ -1,35 +1,51 @@
 use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use anyhow::Result;
+use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let (stack_input, order_input) = input
-        .split_once("\n\n")
-        .ok_or_else(|| anyhow!("invalid input format"))?;
+pub struct Solution;
 
-    let mut st = build_stack(stack_input)?;
+impl Solver for Solution {
+    type Input = (Stacks, Vec<Order>);
 
-    let orders = parse_orders(order_input)?;
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (stack_input, order_input) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow!("invalid input format"))?;
 
-    println!("input stack:");
+        Ok((build_stack(stack_input)?, parse_orders(order_input)?))
+    }
+
+    fn part1((st, orders): &Self::Input) -> Result<Answer> {
+        Ok(rearrange(st.clone(), orders, false).into())
+    }
+
+    fn part2((st, orders): &Self::Input) -> Result<Answer> {
+        Ok(rearrange(st.clone(), orders, true).into())
+    }
+}
+
+register!(2022, 5, Solution);
+
+type Stacks = HashMap<String, VecDeque<String>>;
+
+// rearrange executes orders against the stacks and returns the crates on top of
+// each stack. If all_at_once is set, crates moved together keep their order.
+fn rearrange(mut st: Stacks, orders: &[Order], all_at_once: bool) -> String {
+    debug!("input stack:");
     print_stack(st.iter());
-    println!("\n");
 
     for order in orders {
-        println!();
-
-        println!("{} crate(s): {} => {}", order.count, order.from, order.to);
+        debug!("{} crate(s): {} => {}", order.count, order.from, order.to);
         let mut crates: Vec<_> = st
             .get_mut(&order.from)
             .unwrap()
             .drain(0..(order.count as usize))
             .collect(); // so we can borrow again to insert
 
-        // NOTE: don't want to deal with args rn.
-        // PART ONE: comment the line below
-        crates.reverse(); // now move multiple crates "all at once"
+        if all_at_once {
+            crates.reverse(); // now move multiple crates "all at once"
+        }
 
         // crates are moved one-by-one, reversing them
         let dest = st.get_mut(&order.to).unwrap();
@@ -37,29 +53,22 @@ pub fn run(input: String) -> Result<()> {
             dest.push_front(cr);
         }
 
-        println!();
         print_stack(st.iter());
     }
 
-    println!("\n=====================");
-
     let mut sol: Vec<_> = st.iter().collect();
     sol.sort_by_cached_key(|(k, _)| k.parse::<i32>().unwrap());
 
-    println!("solution:");
+    debug!("solution:");
     print_stack(sol.iter());
 
-    let ans: String = sol
-        .into_iter()
+    sol.into_iter()
         .map(|(_, v)| {
             v.front()
                 .expect("unexpected empty stack")
                 .trim_matches(|c| c == '[' || c == ']')
         })
-        .fold(String::new(), |a, b| a + b);
-    println!("top of all stacks: '{}'", ans);
-
-    Ok(())
+        .fold(String::new(), |a, b| a + b)
 }
 
 // builds a stack by using a guarantee of retangular input to consume
@@ -68,7 +77,7 @@ pub fn run(input: String) -> Result<()> {
 // A more efficient implementation would transform the input like a matrix,
 // rotating it clockwise to walk the rows proceeded with numbers to build this stack.
 // ... but I'm trying to have fun here.
-fn build_stack(stack_input: &str) -> Result<HashMap<String, VecDeque<String>>> {
+fn build_stack(stack_input: &str) -> Result<Stacks> {
     // first, make the input retangular if there's missing trailing spaces
     let mut lines: Vec<String> = stack_input.lines().map(str::to_owned).collect();
     let max = lines.iter().max().unwrap().len();
@@ -90,7 +99,7 @@ fn build_stack(stack_input: &str) -> Result<HashMap<String, VecDeque<String>>> {
         .map(|s| s.expect("input does not support multibyte utf8").trim())
         .partition(|s| s.is_empty() || s.parse::<i64>().is_err());
 
-    let mut stack: HashMap<String, VecDeque<String>> = labels
+    let mut stack: Stacks = labels
         .clone()
         .into_iter()
         .map(str::to_owned)
@@ -116,7 +125,7 @@ fn build_stack(stack_input: &str) -> Result<HashMap<String, VecDeque<String>>> {
     Ok(stack)
 }
 
-struct Order {
+pub struct Order {
     count: i32,
     from: String,
     to: String,
@@ -168,6 +177,6 @@ where
             .iter()
             .map(|s| s.to_owned())
             .collect::<Vec<String>>();
-        println!("{}:\t{}", col.as_ref(), row.join(" "));
+        debug!("{}:\t{}", col.as_ref(), row.join(" "));
     }
 }
@@ -2,7 +2,7 @@ use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::ops::Range;
 
-use anyhow::Result;
+use crate::prelude::*;
 
 enum Either<T> {
     Left(T),
@@ -11,24 +11,44 @@ enum Either<T> {
 
 use Either::*;
 
-const WINDOW_SIZE: usize = 14;
+pub struct Solution;
 
-/// we solve 06 pt 2 by splitting the bytes (no fancy utf8, sorry!)
-/// and comparing them in windows until 12 are different.
-pub fn run(input: String) -> Result<()> {
-    let input = input.trim();
+impl Solver for Solution {
+    type Input = String;
 
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        find_marker(input, 4)
+            .map(Into::into)
+            .ok_or_else(|| anyhow!("no start-of-packet marker found"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        find_marker(input, 14)
+            .map(Into::into)
+            .ok_or_else(|| anyhow!("no start-of-message marker found"))
+    }
+}
+
+register!(2022, 6, Solution);
+
+/// we solve 06 by splitting the bytes (no fancy utf8, sorry!)
+/// and comparing them in windows until window_size are different.
+fn find_marker(input: &str, window_size: usize) -> Option<usize> {
     // fold keeps tracks of # of windows we've looked at,
     // which is the answer: at what character does the message
     // begin?
     // A HashSet is passed along to avoid extra reallocs.
     let (_, result): (HashSet<&u8>, Either<usize>) = input
         .as_bytes()
-        .windows(WINDOW_SIZE)
+        .windows(window_size)
         // sets acc to Right after finding, which halts further adds
         // otherwise acc is Left(#) after run
         .fold(
-            (HashSet::with_capacity(WINDOW_SIZE), Left(WINDOW_SIZE)),
+            (HashSet::with_capacity(window_size), Left(window_size)),
             |acc, sl| match acc {
                 (mut hs, Left(acc)) => {
                     hs.clear();
@@ -46,18 +66,21 @@ pub fn run(input: String) -> Result<()> {
         );
 
     match result {
-        Left(pos) => println!("read {} characters and failed to find start message", pos),
+        Left(pos) => {
+            debug!("read {} characters and failed to find start message", pos);
+            None
+        }
         Right(pos) => {
-            println!("found message after {} characters:", pos);
-            println!(
+            debug!("found message after {} characters:", pos);
+            debug!(
                 "...{}_{}_{}...",
-                str_with_context(input, pos - WINDOW_SIZE * 2..pos),
-                &input[pos - WINDOW_SIZE..pos],
-                str_with_context(input, pos..pos + WINDOW_SIZE * 2)
+                str_with_context(input, pos.saturating_sub(window_size * 2)..pos),
+                &input[pos - window_size..pos],
+                str_with_context(input, pos..pos + window_size * 2)
             );
+            Some(pos)
         }
     }
-    Ok(())
 }
 
 fn str_with_context<I: Borrow<str>>(s: I, mut rng: Range<usize>) -> String {
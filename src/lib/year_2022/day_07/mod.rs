@@ -2,35 +2,52 @@ mod tree;
 
 use crate::prelude::*;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-pub fn run(input: String) -> Result<()> {
-    let fs = repl(input.split('\n').map(str::to_string).collect())?;
+pub struct Solution;
 
-    let path_to_size: HashMap<_, _> = fs
-        .iter()
-        .filter_map(|(p, n)| match n {
-            tree::Child::Folder(n) => Some((p, n)),
-            _ => None,
-        })
-        .map(|(p, n)| (p, n.size()))
-        .collect();
+impl Solver for Solution {
+    type Input = tree::Filesystem;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        repl(input.split('\n').map(str::to_string).collect())
+    }
 
     // part 1: sum all directories with total size <= 100_000
     // this solution sucks... O(n^2)
-    let soln = path_to_size
-        .values()
-        .filter(|s| **s <= 100_000)
-        .sum::<usize>();
+    fn part1(fs: &Self::Input) -> Result<Answer> {
+        let soln = folder_sizes(fs)
+            .values()
+            .filter(|s| **s <= 100_000)
+            .sum::<usize>();
 
-    println!("combined size of nodes <= 100k: {}", soln);
+        Ok(soln.into())
+    }
 
     // part 2: of our 70M, we need 30M free. Determine free space and find smallest node
     // to delete to achieve 30M free.
-    let deficit = fs.size() - 40_000_000;
-    let soln = path_to_size.values().filter(|s| **s >= deficit).min();
-    println!("smallest directory size >= 30_000_000: {}", soln.unwrap());
+    fn part2(fs: &Self::Input) -> Result<Answer> {
+        let deficit = fs.size() - 40_000_000;
+        let soln = folder_sizes(fs)
+            .into_values()
+            .filter(|s| *s >= deficit)
+            .min()
+            .ok_or_else(|| anyhow!("no directory frees enough space"))?;
 
-    Ok(())
+        Ok(soln.into())
+    }
+}
+
+register!(2022, 7, Solution);
+
+// maps every folder's path to its total size
+fn folder_sizes(fs: &tree::Filesystem) -> HashMap<PathBuf, usize> {
+    fs.iter()
+        .filter_map(|(p, n)| match n {
+            tree::Child::Folder(n) => Some((p, n.size())),
+            _ => None,
+        })
+        .collect()
 }
 
 // repl runs some given input and returns the filesystem as represented
@@ -47,7 +64,7 @@ pub fn repl(input: Vec<String>) -> Result<tree::Filesystem> {
         match parts.as_slice() {
             &["$", "ls", path] | &["$", "dir", path] => {
                 target = Some(path.to_string());
-                println!("cwd: {}\ttarget: {:?}", cwd, target);
+                trace!("cwd: {}\ttarget: {:?}", cwd, target);
             }
             &["$", "dir"] => {
                 target = None;
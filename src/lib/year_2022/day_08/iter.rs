@@ -1,25 +1,6 @@
 use std::iter::repeat;
 
-#[derive(Copy, Clone, Debug)]
-pub struct Coordinate {
-    pub x: usize,
-    pub y: usize,
-}
-
-impl From<(usize, usize)> for Coordinate {
-    fn from(c: (usize, usize)) -> Self {
-        Self { x: c.0, y: c.1 }
-    }
-}
-
-impl From<(i32, i32)> for Coordinate {
-    fn from(c: (i32, i32)) -> Self {
-        Self {
-            x: c.0 as usize,
-            y: c.1 as usize,
-        }
-    }
-}
+pub type Coordinate = crate::coord::Coordinate<usize>;
 
 // rays_from_point provides an iterator which yields coords from cardinal directions
 // at the provied point.
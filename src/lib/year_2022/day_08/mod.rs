@@ -1,109 +1,91 @@
 mod iter;
 
-use anyhow::{anyhow, Result};
+use crate::grid::Grid;
+use crate::prelude::*;
 
 use iter::rays_from_point;
 use std::iter::repeat;
 
-pub fn run(input: String) -> Result<()> {
-    let vis_map = get_vismap(&input)?;
+pub struct Solution;
 
-    println!("visibility map:");
-    for x in 0..vis_map.len() {
-        for y in 0..vis_map.len() {
-            if *vis_map.get(x).unwrap().get(y).unwrap() {
-                print!("t")
-            } else {
-                print!("f")
-            }
-        }
-        println!()
+impl Solver for Solution {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_trees(input)?;
+        Ok(input.to_string())
     }
 
-    println!("number of visible trees: {}", count_vismap(&vis_map));
-
-    let scenic_map = scenic_score_map(&input)?;
-    let max: ((usize, usize), u32) = scenic_map
-        .iter()
-        .enumerate()
-        .flat_map(|(x, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(y, score)| ((x, y), *score))
-        })
-        .max_by_key(|(_, s)| *s)
-        .unwrap();
-
-    println!("scenic score map:");
-    for x in 0..vis_map.len() {
-        for y in 0..vis_map.len() {
-            print!("({:>3})", scenic_map.get(x).unwrap().get(y).unwrap());
-        }
-        println!()
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let vis_map = get_vismap(input)?;
+
+        debug!(
+            "visibility map:\n{}",
+            vis_map.map(|v| if *v { 't' } else { 'f' })
+        );
+
+        Ok(count_vismap(&vis_map).into())
     }
 
-    println!(
-        "tree with highest scenic score has {} at ({}, {})",
-        max.1, max.0 .0, max.0 .1
-    );
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let scenic_map = scenic_score_map(input)?;
+        let max: ((usize, usize), u32) = scenic_map
+            .iter()
+            .map(|(c, score)| (c, *score))
+            .max_by_key(|(_, s)| *s)
+            .ok_or_else(|| anyhow!("no trees"))?;
+
+        debug!(
+            "scenic score map:\n{}",
+            scenic_map.map(|s| format!("({:>3})", s))
+        );
+
+        debug!(
+            "tree with highest scenic score is at ({}, {})",
+            max.0 .0, max.0 .1
+        );
 
-    Ok(())
+        Ok(max.1.into())
+    }
 }
 
-fn parse_trees(input: &str) -> Result<Vec<Vec<u8>>> {
-    input
-        .split('\n')
-        .filter(|row| !row.is_empty())
-        .map(|row| {
-            row.split("")
-                .filter(|col| !col.is_empty())
-                .map(|v| {
-                    v.parse()
-                        .map_err(|e| anyhow!("failed to parse '{}': {}", v, e))
-                })
-                .collect::<Result<Vec<u8>>>()
-        })
-        .collect::<Result<Vec<_>>>()
+register!(2022, 8, Solution);
+
+fn parse_trees(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|d| d as u8)
+            .ok_or_else(|| anyhow!("failed to parse '{}' as a tree height", c))
+    })
 }
 
 // get vismap returns a truth map of what trees are visible from
-// the edge by !! (y => x => true)
-fn get_vismap(input: &str) -> Result<Vec<Vec<bool>>> {
+// the edge by !!
+fn get_vismap(input: &str) -> Result<Grid<bool>> {
     let grid = parse_trees(input)?;
 
     // array of bits for whether a tree is visible
-    let mut vis_map: Vec<Vec<bool>> = Vec::with_capacity(grid.len());
-    let width = grid.iter().map(|x| x.len()).max().unwrap();
-    let dimens = (width as usize, grid.len() as usize);
-
-    for _ in 0..grid.len() {
-        let mut row = vec![];
-        row.resize(width, false);
-        vis_map.push(row);
-    }
+    let mut vis_map = Grid::<bool>::new(grid.width(), grid.height());
+    let (width, height) = grid.dimensions();
 
-    let edgepoints = (0..grid.len())
+    let edgepoints = (0..height)
         .zip(repeat(0))
         .chain(repeat(0).zip(1..width))
-        .chain(repeat(width - 1).zip(1..grid.len()))
-        .chain((1..width).zip(repeat(grid.len() - 1)))
+        .chain(repeat(width - 1).zip(1..height))
+        .chain((1..width).zip(repeat(height - 1)))
         .map(Into::into)
         .collect::<Vec<iter::Coordinate>>();
 
     for src in edgepoints {
-        let start = grid.get(src.y).unwrap().get(src.x).unwrap();
-        *vis_map.get_mut(src.y).unwrap().get_mut(src.x).unwrap() = true;
+        let start = &grid[(src.x, src.y)];
+        vis_map[(src.x, src.y)] = true;
 
         // walk the edges and then ray out.
-        for dir in rays_from_point(dimens, src) {
+        for dir in rays_from_point(grid.dimensions(), src) {
             let mut last = Some(start);
 
             for (x, y) in dir {
-                let tree = grid
-                    .get(y)
-                    .unwrap_or_else(|| panic!("failed to get y={}", y))
-                    .get(x)
-                    .unwrap_or_else(|| panic!("failed to get x @ ({}, {})", x, y));
+                let tree = &grid[(x, y)];
 
                 // println!(
                 //     "({}, {}) ==> ({}, {}): {} >= {:?}",
@@ -114,45 +96,17 @@ fn get_vismap(input: &str) -> Result<Vec<Vec<bool>>> {
                     _ => (),
                 }
 
-                let vm_row = vis_map.get_mut(y).unwrap();
-                *vm_row
-                    .get_mut(x)
-                    .unwrap_or_else(|| panic!("could not fetch ({},{}) from vismap", x, y)) = true;
+                vis_map[(x, y)] = true;
                 last = Some(tree);
             }
         }
-
-        println!("===");
     }
 
-    /*
-    println!("=================================");
-    for dir in iter_dirs(&grid, None) {
-        let mut last: Option<u8> = None;
-        for ((x, y), tree) in dir {
-            let vm_row = vis_map.get_mut(y).unwrap();
-            match last {
-                Some(l) if l >= tree => continue,
-                _ => (),
-            }
-
-            *vm_row
-                .get_mut(x)
-                .expect(&format!("could not fetch ({},{}) from vismap", x, y)) = true;
-            last = Some(tree);
-        }
-    }
-    */
-
     Ok(vis_map)
 }
 
-fn count_vismap<T: std::borrow::Borrow<Vec<Vec<bool>>>>(vismap: T) -> usize {
-    vismap
-        .borrow()
-        .iter()
-        .flat_map(|vec| vec.iter().filter(|cell| **cell))
-        .count()
+fn count_vismap<T: std::borrow::Borrow<Grid<bool>>>(vismap: T) -> usize {
+    vismap.borrow().positions(|visible| *visible).count()
 }
 
 #[test]
@@ -198,18 +152,12 @@ ttt"#;
 }
 
 #[cfg(test)]
-fn assert_trees_eq<T: std::borrow::Borrow<Vec<Vec<bool>>>>(expected: &str, actual: T) {
+fn assert_trees_eq<T: std::borrow::Borrow<Grid<bool>>>(expected: &str, actual: T) {
     let res = actual
         .borrow()
-        .iter()
-        .map(|row| {
-            row.iter()
-                .map(|t| if *t { "t" } else { "f" })
-                .collect::<Vec<_>>()
-                .join("")
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
+        .map(|t| if *t { "t" } else { "f" })
+        .to_string();
+    let res = res.trim_end();
 
     println!("expected:\n{}\n\nactual:\n{}", expected, res);
     assert_eq!(expected, res);
@@ -248,42 +196,32 @@ ttttt"#;
 // Pt 2 follows. it bad.
 
 // calculate how many trees are visible from a position
-fn scenic_score_map(input: &str) -> Result<Vec<Vec<u32>>> {
+fn scenic_score_map(input: &str) -> Result<Grid<u32>> {
     let grid = parse_trees(input)?;
-
-    let mut scenic_map: Vec<Vec<u32>> = Vec::with_capacity(grid.len());
-    let width = grid.iter().map(|x| x.len()).max().unwrap();
-    for _ in 0..grid.len() {
-        scenic_map.push(vec![0; width]);
-    }
-    let dimens = (width, grid.len());
+    let mut scenic_map = Grid::<u32>::new(grid.width(), grid.height());
 
     // O(N^2) yikes. optimizing this after writing a dumb pt 1 feels even worse :D
-    for y in 0..grid.len() {
-        for x in 0..width {
-            let mut vis = 1;
-            let our_size = grid.get(y).unwrap().get(x).unwrap();
-
-            for dir in rays_from_point(dimens, (x, y).into()) {
-                // product of all directions, sum this line
-                let mut line = 0;
-                for (tx, ty) in dir {
-                    let t = grid.get(ty).unwrap().get(tx).unwrap();
-
-                    line += 1;
-                    if t >= our_size {
-                        break;
-                    }
-                }
-
-                if line != 0 {
-                    vis *= line;
+    for ((x, y), our_size) in grid.iter() {
+        let mut vis = 1;
+
+        for dir in rays_from_point(grid.dimensions(), (x, y).into()) {
+            // product of all directions, sum this line
+            let mut line = 0;
+            for (tx, ty) in dir {
+                let t = &grid[(tx, ty)];
+
+                line += 1;
+                if t >= our_size {
+                    break;
                 }
             }
 
-            let row = scenic_map.get_mut(y).unwrap();
-            *row.get_mut(x).unwrap() = vis;
+            if line != 0 {
+                vis *= line;
+            }
         }
+
+        scenic_map[(x, y)] = vis;
     }
 
     Ok(scenic_map)
@@ -339,7 +277,7 @@ fn test_examples_scenic() {
 35390"#;
 
     let scores = scenic_score_map(test).unwrap();
-    assert_eq!(4, *scores.get(1).unwrap().get(2).unwrap());
+    assert_eq!(4, scores[(2, 1)]);
 
     let test = r#"30373
 25512
@@ -347,12 +285,13 @@ fn test_examples_scenic() {
 33549
 35390"#;
     let scores = scenic_score_map(test).unwrap();
-    assert_eq!(8, *scores.get(3).unwrap().get(2).unwrap());
+    assert_eq!(8, scores[(2, 3)]);
 }
 
 #[cfg(test)]
-fn assert_scenic_tree_eq(expected: Vec<Vec<u32>>, actual: Vec<Vec<u32>>) {
-    let zip_rows = expected.iter().enumerate().zip(actual.iter());
+fn assert_scenic_tree_eq(expected: Vec<Vec<u32>>, actual: Grid<u32>) {
+    assert_eq!((expected[0].len(), expected.len()), actual.dimensions());
+    let zip_rows = expected.iter().enumerate().zip(actual.rows());
     let mut failed: Option<(usize, usize, u32, u32)> = None;
     for ((ey, erow), arow) in zip_rows {
         if failed.is_some() {
@@ -376,7 +315,7 @@ fn assert_scenic_tree_eq(expected: Vec<Vec<u32>>, actual: Vec<Vec<u32>>) {
         }
 
         println!("\nactual:");
-        for arow in actual {
+        for arow in actual.rows() {
             for score in arow {
                 print!("({:>3})", score);
             }
@@ -1,54 +1,51 @@
-use std::cmp::{max, min};
-use std::collections::HashSet;
 use std::iter::repeat;
-use std::str::FromStr;
 
-use anyhow::{bail, Error, Result};
+use crate::{
+    coord::{Coordinate, Direction, Orientation},
+    prelude::*,
+    sparse_grid::SparseGrid,
+};
 
-pub fn run(input: String) -> Result<()> {
-    let orders = parse_orders(&input)?;
-    let mut m = Map::new(1);
-
-    // println!("{}", m);
-    for o in &orders {
-        m.execute(*o);
-        // println!("{}", m);
-    }
+pub struct Solution;
 
-    println!("visited: {}", m.visited.len());
+impl Solver for Solution {
+    type Input = Vec<Order>;
 
-    let mut m = Map::new(9);
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_orders(input)
+    }
 
-    // println!("{}", m);
-    for o in orders {
-        m.execute(o);
-        // println!("{}", m);
+    fn part1(orders: &Self::Input) -> Result<Answer> {
+        Ok(simulate(orders, 1).into())
     }
 
-    println!("visited, pt 2: {}", m.visited.len());
-    Ok(())
+    fn part2(orders: &Self::Input) -> Result<Answer> {
+        Ok(simulate(orders, 9).into())
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Coordinate {
-    x: i32,
-    y: i32,
-}
+register!(2022, 9, Solution);
 
-impl Coordinate {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
+// executes all orders on a rope with the provided number of trailing knots,
+// returning the number of positions the tail visited.
+fn simulate(orders: &[Order], knots: usize) -> usize {
+    let mut m = Map::new(knots);
 
-    fn offset(&mut self, x: i32, y: i32) {
-        self.x += x;
-        self.y += y;
+    for o in orders {
+        m.execute(*o);
     }
+    debug!("tail visited:\n{}", m.visited);
+
+    m.visited.len()
 }
 
+// the rope moves around a graph, so up is y + 1
+const ORIENTATION: Orientation = Orientation::YUp;
+
 struct Map {
-    knots: Vec<Coordinate>,
-    visited: HashSet<Coordinate>,
+    knots: Vec<Coordinate<i64>>,
+    // every position the tail has been, marked with '#'
+    visited: SparseGrid<char>,
 }
 
 impl Map {
@@ -59,131 +56,57 @@ impl Map {
 
         Self {
             knots: repeat(Coordinate::new(0, 0)).take(knots + 1).collect(),
-            visited: HashSet::new(),
+            visited: SparseGrid::new().with_orientation(ORIENTATION),
         }
     }
 
     /// execute moves the head according to order.
     /// the tail and visited are updated accordingly
     fn execute(&mut self, o: Order) {
-        let (dx, dy) = match o {
-            Up => (0, 1),
-            Right => (1, 0),
-            Down => (0, -1),
-            Left => (-1, 0),
-        };
         // the head knot is first, we then update
         // all remaining knots to follow.
-        self.knots.get_mut(0).unwrap().offset(dx, dy);
+        *self.knots.get_mut(0).unwrap() += o.delta(ORIENTATION);
 
         for (headi, taili) in (0..self.knots.len()).zip(1..self.knots.len()) {
             let head = self.knots.get(headi).unwrap();
             let tail = self.knots.get(taili).unwrap();
 
-            let newtail = self.update_pair(head, tail.clone());
+            let newtail = self.update_pair(head, *tail);
             *self.knots.get_mut(taili).unwrap() = newtail;
         }
 
-        self.visited.insert(self.knots.last().unwrap().clone());
+        self.visited.insert(*self.knots.last().unwrap(), '#');
     }
 
-    // modifies the psat tail given a moved head
-    fn update_pair(&self, head: &Coordinate, mut tail: Coordinate) -> Coordinate {
-        match (head.x - tail.x, head.y - tail.y) {
-            // touching, OK!
-            (dx, dy) if dx.abs() <= 1 && dy.abs() <= 1 => return tail,
-            (0, 2) => tail.offset(0, 1),
-            (0, -2) => tail.offset(0, -1),
-            (2, 0) => tail.offset(1, 0),
-            (-2, 0) => tail.offset(-1, 0),
-            (-2, -2) => tail.offset(-1, -1),
-            (-2, 2) => tail.offset(-1, 1),
-            (2, -2) => tail.offset(1, -1),
-            (2, 2) => tail.offset(1, 1),
-            (1, 2) | (2, 1) => tail.offset(1, 1),
-            (-2, 1) | (-1, 2) => tail.offset(-1, 1),
-            (2, -1) | (1, -2) => tail.offset(1, -1),
-            (-2, -1) | (-1, -2) => tail.offset(-1, -1),
-            (dx, dy) => panic!("unknown case ({}, {}): {:?} => {:?}", dx, dy, head, tail),
+    // modifies the past tail given a moved head. Once the head isn't touching,
+    // the tail moves a step towards it, diagonally if needed.
+    fn update_pair(&self, head: &Coordinate<i64>, tail: Coordinate<i64>) -> Coordinate<i64> {
+        if head.chebyshev(&tail) <= 1 {
+            return tail;
         }
 
-        tail
+        let d = *head - tail;
+        tail + Coordinate::new(d.x.signum(), d.y.signum())
     }
 }
 
 impl std::fmt::Display for Map {
+    // draws the knots over where the tail has been, numbered from the head at 0
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let mut px = 10;
-        let mut py = 10;
-        let mut nx = 0;
-        let mut ny = 0;
-
-        for knot in &self.knots {
-            px = max(knot.x, px) + 1;
-            py = max(knot.y, py) + 1;
-            nx = min(knot.x, nx) - 1;
-            ny = min(knot.y, ny) - 1;
-        }
-
-        let mut output = vec![];
-        output.resize((px - nx) as usize, vec![]);
-        for row in &mut output {
-            row.resize((py - ny) as usize, ".".to_string());
-        }
-
-        // render in quadrant 1, flip bounds
-        for y in (ny..py).rev() {
-            for x in nx..px {
-                for (i, knot) in self.knots.iter().enumerate() {
-                    let c = output
-                        .get_mut((x - nx) as usize)
-                        .unwrap()
-                        .get_mut((y - ny) as usize)
-                        .unwrap();
-
-                    if knot.x == x && knot.y == y {
-                        *c = i.to_string();
-                    }
-                }
-            }
+        let mut g = self.visited.clone();
+        g.insert(Coordinate::new(0, 0), 's');
+        for (i, knot) in self.knots.iter().enumerate().rev() {
+            g.insert(*knot, char::from_digit(i as u32, 36).unwrap_or('?'));
         }
 
-        for row in output {
-            for c in row {
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-
-        std::fmt::Result::Ok(())
+        write!(f, "{}", g)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Order {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-use Order::*;
-
-impl FromStr for Order {
-    type Err = Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "U" => Ok(Up),
-            "R" => Ok(Right),
-            "D" => Ok(Down),
-            "L" => Ok(Left),
-            order => bail!("unknown order: {}", order),
-        }
-    }
-}
+pub type Order = Direction;
 
 /// parse_orders takes a string of newline-separated orders
-/// and parses them out into the enum above.
+/// and parses them out into a direction for each step.
 fn parse_orders(input: &str) -> Result<Vec<Order>> {
     input
         .split('\n')
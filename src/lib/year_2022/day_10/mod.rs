@@ -1,38 +1,47 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Error, Result};
+use crate::prelude::*;
 
 const MEASURED_POINTS: [usize; 6] = [20, 60, 100, 140, 180, 220];
 
-pub fn run(input: String) -> Result<()> {
-    let ops = parse_ops(input).unwrap();
-    let c = process_ops(ops);
+pub struct Solution;
 
-    println!(
-        "signal strength sum: {}",
-        MEASURED_POINTS
+impl Solver for Solution {
+    type Input = Vec<Op>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_ops(input)
+    }
+
+    fn part1(ops: &Self::Input) -> Result<Answer> {
+        let c = process_ops(ops.clone());
+
+        let sum = MEASURED_POINTS
             .iter()
             .map(|cycle| c.get_value_at_cycle(*cycle).map(|v| v * *cycle as i32))
-            .try_fold(0, |acc, c| c.map(|c| c + acc))?,
-    );
+            .try_fold(0, |acc, c| c.map(|c| c + acc))?;
 
-    for row in c.display {
-        for c in row {
-            if c {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
+        Ok(sum.into())
     }
 
-    Ok(())
+    fn part2(ops: &Self::Input) -> Result<Answer> {
+        let c = process_ops(ops.clone());
+
+        let screen = c
+            .display
+            .iter()
+            .map(|row| row.iter().map(|c| if *c { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>();
+
+        Ok(screen.into())
+    }
 }
 
+register!(2022, 10, Solution);
+
 #[derive(Debug, Clone)]
-enum Op {
+pub enum Op {
     Addx(i32),
     Noop,
 }
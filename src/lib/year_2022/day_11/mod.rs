@@ -1,69 +1,73 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail, Error, Result};
+use crate::prelude::*;
 use indicatif::{ProgressBar, ProgressStyle};
 
-pub fn run(input: String) -> Result<()> {
-    let mut monkies = parse_monkies(&input)?;
+pub struct Solution;
 
-    for _ in 0..20 {
-        monkey_a_round(&mut monkies, Some(3), None);
+impl Solver for Solution {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_monkies(input)
     }
 
-    println!(
-        "{}",
-        monkies
-            .iter()
-            .map(|m| format!("{}", m))
-            .fold("".to_string(), |acc, s| acc + "\n" + &s)
-    );
+    fn part1(monkies: &Self::Input) -> Result<Answer> {
+        let mut monkies = monkies.clone();
 
-    println!("\n");
+        for _ in 0..20 {
+            monkey_a_round(&mut monkies, Some(3), None);
+        }
 
-    println!(
-        "{}",
-        monkies
-            .iter()
-            .map(|m| format!("Monkey {} inspected items {} times", m.index, m.inspections))
-            .fold("".to_string(), |acc, s| acc + "\n" + &s),
-    );
+        debug!(
+            "{}",
+            monkies
+                .iter()
+                .map(|m| format!("{}", m))
+                .fold("".to_string(), |acc, s| acc + "\n" + &s)
+        );
 
-    let mut inspections = monkies.iter().map(|m| m.inspections).collect::<Vec<_>>();
-    inspections.sort();
+        debug!(
+            "{}",
+            monkies
+                .iter()
+                .map(|m| format!("Monkey {} inspected items {} times", m.index, m.inspections))
+                .fold("".to_string(), |acc, s| acc + "\n" + &s),
+        );
 
-    println!("\n");
+        Ok(monkey_business(&monkies).into())
+    }
 
-    println!(
-        "Monkey business level: {}",
-        inspections.iter().rev().take(2).product::<usize>(),
-    );
+    fn part2(monkies: &Self::Input) -> Result<Answer> {
+        let mut monkies = monkies.clone();
+        let worry_modulus = monkies.iter().map(|m| m.test_divisor).product::<u32>();
+        let pb = ProgressBar::new(10000);
+        pb.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>5}/{len:5} {msg}",
+            )
+            .unwrap()
+            .progress_chars("#>-"),
+        );
 
-    println!("=================\nPart 2\n=================");
-    let mut monkies = parse_monkies(&input)?;
-    let worry_modulus = monkies.iter().map(|m| m.test_divisor).product::<u32>();
-    let pb = ProgressBar::new(10000);
-    pb.set_style(
-        ProgressStyle::with_template(
-            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>5}/{len:5} {msg}",
-        )
-        .unwrap()
-        .progress_chars("#>-"),
-    );
+        for i in 0..10000 {
+            pb.set_position(i);
+            monkey_a_round(&mut monkies, None, Some(worry_modulus));
+        }
+        pb.finish();
 
-    for i in 0..10000 {
-        pb.set_position(i);
-        monkey_a_round(&mut monkies, None, Some(worry_modulus));
+        Ok(monkey_business(&monkies).into())
     }
-    pb.finish();
+}
+
+register!(2022, 11, Solution);
 
+// the product of the two largest inspection counts
+fn monkey_business(monkies: &[Monkey]) -> usize {
     let mut inspections = monkies.iter().map(|m| m.inspections).collect::<Vec<_>>();
     inspections.sort();
-    println!(
-        "Monkey business level: {}",
-        inspections.iter().rev().take(2).product::<usize>(),
-    );
 
-    Ok(())
+    inspections.iter().rev().take(2).product::<usize>()
 }
 
 /// do a round of item calculations with passed monkies
@@ -148,7 +152,7 @@ impl Op {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<u128>,
     op: Op,
     test_divisor: u32,
@@ -2,8 +2,7 @@ use std::fmt;
 use std::io::{self, Write};
 use std::str::FromStr;
 
-use crate::{map::Map as BaseMap, prelude::*};
-use anyhow::{bail, Error, Result};
+use crate::{grid::Grid, prelude::*};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Tile {
@@ -13,15 +12,15 @@ pub enum Tile {
 }
 use Tile::*;
 
-impl FromStr for Tile {
-    type Err = Error;
+impl TryFrom<char> for Tile {
+    type Error = Error;
 
-    fn from_str(s: &str) -> Result<Self> {
-        match s.chars().collect::<Vec<_>>()[..] {
-            ['S'] => Ok(Start),
-            ['E'] => Ok(End),
-            [c @ 'a'..='z'] => Ok(Walkable(c as u8 - b'a')),
-            _ => bail!("invalid input to parse for Tile: {:?}", s),
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            'S' => Ok(Start),
+            'E' => Ok(End),
+            'a'..='z' => Ok(Walkable(c as u8 - b'a')),
+            _ => bail!("invalid input to parse for Tile: {:?}", c),
         }
     }
 }
@@ -36,93 +35,11 @@ impl fmt::Display for Tile {
     }
 }
 
-#[derive(Debug, Clone, Deref, DerefMut)]
-pub struct Map<T>(BaseMap<T>);
-
-impl<T: Default + Clone> Map<T> {
-    pub fn new_dense(width: usize, height: usize) -> Self {
-        Self(BaseMap::new_dense(width, height))
-    }
-}
-
-impl FromStr for Map<Tile> {
+impl FromStr for Grid<Tile> {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let m = BaseMap::from_data(
-            s.split('\n')
-                .filter(|l| !l.is_empty())
-                .map(|row| {
-                    row.split("")
-                        .filter(|c| !c.is_empty())
-                        .map(FromStr::from_str)
-                        // .map(|c| c.map_err(|e| anyhow!("error '{}' on parsing row: {}", e, row)))
-                        .collect::<Result<Vec<_>>>()
-                })
-                .collect::<Result<_>>()?,
-        )?;
-
-        if m.iter_rows().any(|row| row.len() != m.dimensions.0) {
-            bail!("all rows must be the same width");
-        }
-
-        Ok(Map(m))
-    }
-}
-
-impl<T: fmt::Display> fmt::Display for Map<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for row in self.iter_rows() {
-            for cell in row {
-                write!(f, "{}", cell)?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
-    }
-}
-
-// Specialization of Map<bool> which caches its score
-// until mutated
-#[derive(Debug, Clone)]
-pub struct VisitedMap {
-    map: Map<bool>,
-    score: usize, // keeps track of # of tiles set
-}
-
-impl VisitedMap {
-    pub fn new(width: usize, height: usize) -> Self {
-        Self {
-            map: Map::<bool>::new_dense(width, height),
-            score: 0,
-        }
-    }
-
-    pub fn score(&self) -> usize {
-        self.score - 1 // don't count start
-    }
-
-    pub fn get(&self, x: usize, y: usize) -> Result<&bool> {
-        self.map.get(x, y)
-    }
-
-    pub fn set(&mut self, x: usize, y: usize) -> Result<()> {
-        *self.map.get_mut(x, y)? = true;
-        self.score += 1;
-        Ok(())
-    }
-
-    pub fn unset(&mut self, x: usize, y: usize) -> Result<()> {
-        *self.map.get_mut(x, y)? = false;
-        self.score -= 1;
-        Ok(())
-    }
-}
-
-impl fmt::Display for VisitedMap {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.map)
+        Grid::parse(s, Tile::try_from)
     }
 }
 
@@ -1,52 +1,70 @@
 mod map;
-mod pathings;
+pub mod pathings;
 
-use anyhow::Result;
+use crate::prelude::*;
 
-use map::{Map, Tile};
+use crate::{grid::Grid, search};
+use map::Tile;
+use pathings::Hill;
 
-pub fn run(input: String) -> Result<()> {
-    let map: Map<Tile> = input.as_str().parse()?;
-    println!("evaluating map:\n{}", map);
-    let path = pathings::find_shortest_path_dijkstra(&map).expect("should have found a path");
-    println!("found path: \n{}", path);
+pub struct Solution;
 
-    println!("====== part 2 ======");
-    let shortest = pathings::find(&map, |t| *t == Tile::Walkable(0)) // a == 0 cost
-        .into_iter()
-        .filter_map(|(x, y)| pathings::find_shortest_path_dijkstra_from(&map, x, y))
-        .min_by_key(|p| p.score());
-    println!(
-        "shortest path step cost: {}",
-        shortest.expect("should have found a path").score()
-    );
+impl Solver for Solution {
+    type Input = Grid<Tile>;
 
-    Ok(())
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-#[test]
-fn map_ex_brute() {
-    let input = r#"Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi"#;
-    let map: Map<Tile> = input.parse().expect("should have parsed the map");
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        debug!("evaluating map:\n{}", map);
+        let hill = Hill::new(map).context("map has no end")?;
+        let start = pathings::find_start(map);
+        let path = search::astar(&hill, [start], |p| *p == hill.end())
+            .context("should have found a path")?;
+        debug!("found path: {:?}", path.nodes);
 
-    let path = pathings::find_shortest_path_brute(&map).expect("should have found a path");
+        Ok(path.cost.into())
+    }
 
-    assert_eq!(31, path.score());
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        // search from every lowest point at once
+        let hill = Hill::new(map).context("map has no end")?;
+        let starts = pathings::find(map, |t| matches!(t, Tile::Start | Tile::Walkable(0)));
+        let path =
+            search::bfs(&hill, starts, |p| *p == hill.end()).context("should have found a path")?;
+
+        Ok(path.cost.into())
+    }
 }
 
-#[test]
-fn map_ex_dijkstra() {
-    let input = r#"Sabqponm
+register!(2022, 12, Solution);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"Sabqponm
 abcryxxl
 accszExk
 acctuvwj
 abdefghi"#;
-    let map: Map<Tile> = input.parse().expect("should have parsed the map");
 
-    let path = pathings::find_shortest_path_dijkstra(&map).expect("should have found a path");
-    assert_eq!(31, path.score());
+    #[test]
+    fn test_strategies_agree() {
+        let map: Grid<Tile> = EXAMPLE.parse().expect("should have parsed the map");
+        let hill = Hill::new(&map).unwrap();
+        let start = pathings::find_start(&map);
+        let end = |p: &(usize, usize)| *p == hill.end();
+
+        let bfs = search::bfs(&hill, [start], end).unwrap();
+        let dijkstra = search::dijkstra(&hill, [start], end).unwrap();
+        let astar = search::astar(&hill, [start], end).unwrap();
+        assert_eq!(31, bfs.cost);
+        assert_eq!(31, dijkstra.cost);
+        assert_eq!(31, astar.cost);
+        assert_eq!(31, astar.steps());
+        assert_eq!((0, 0), *astar.start());
+        assert_eq!((5, 2), *astar.end());
+    }
 }
@@ -2,35 +2,44 @@ mod equality;
 mod parse;
 mod value;
 
-use anyhow::Result;
+use crate::prelude::*;
 use std::fmt;
 
 use value::{Value, Value::*};
 
-pub fn run(input: String) -> Result<()> {
-    let mut lines = parse_input(&input)?;
-    println!("sum of sorted indices: {}", indices_sum(&lines)?);
+pub struct Solution;
 
-    println!("\n======= part 2 =========\n");
+impl Solver for Solution {
+    type Input = Vec<Value>;
 
-    let two = parse::line("[[2]]")?;
-    let six = parse::line("[[6]]")?;
-    lines.push(two.clone());
-    lines.push(six.clone());
-    lines.sort();
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Answer> {
+        Ok(indices_sum(lines)?.into())
+    }
 
-    println!(
-        "decoder key: {}",
-        lines
+    fn part2(lines: &Self::Input) -> Result<Answer> {
+        let mut lines = lines.clone();
+        let two = parse::line("[[2]]")?;
+        let six = parse::line("[[6]]")?;
+        lines.push(two.clone());
+        lines.push(six.clone());
+        lines.sort();
+
+        let key = lines
             .iter()
             .enumerate()
             .filter(|(_, p)| **p == two || **p == six)
-            .fold(1, |acc, (i, _)| { acc * i })
-    );
+            .fold(1, |acc, (i, _)| acc * (i + 1));
 
-    Ok(())
+        Ok(key.into())
+    }
 }
 
+register!(2022, 13, Solution);
+
 #[test]
 fn test_equality_simple_cases() {
     let cases = vec![
@@ -90,7 +99,7 @@ fn parse_input(input: &str) -> Result<Vec<Value>> {
     input
         .split("\n\n")
         .filter(|l| !l.is_empty())
-        .flat_map(|l| l.split('\n'))
+        .flat_map(str::lines)
         .map(parse::line)
         .collect::<Result<Vec<_>>>()
 }
@@ -4,11 +4,11 @@ use std::fmt;
 use std::iter::repeat;
 use std::str::FromStr;
 
-pub use crate::{coord::Coordinate as BaseCoordinate, map::Map as BaseMap, prelude::*};
+pub use crate::{coord::Coordinate as BaseCoordinate, prelude::*, sparse_grid::SparseGrid};
 
-type Coordinate = BaseCoordinate<usize>;
+type Coordinate = BaseCoordinate<i64>;
 
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
 pub enum Tile {
     Source,
     Rock,
@@ -18,76 +18,42 @@ pub enum Tile {
 }
 use Tile::*;
 
-#[derive(Debug, Clone, Deref, DerefMut)]
+// Map is the cave's cross section, with y increasing downwards. Only tiles which
+// aren't empty are stored, so it doesn't need resizing as sand piles up.
+#[derive(Debug, Clone, Default)]
 pub struct Map {
-    data: BaseMap<Tile>,
+    data: SparseGrid<Tile>,
 }
 
 impl Map {
-    pub fn get(&self, x: usize, y: usize) -> Result<&Tile> {
-        self.data.get(x, y)
-    }
-
-    pub fn get_mut(&mut self, x: usize, y: usize) -> Result<&mut Tile> {
-        self.data.get_mut(x, y)
-    }
-
-    pub fn resize(&mut self, width: usize, height: usize) {
-        self.data.resize(width, height)
-    }
+    // get returns the tile at (x, y), or None if it's below everything on the map
+    // so that anything there falls into the abyss
+    pub fn get(&self, x: i64, y: i64) -> Option<Tile> {
+        let (_, max) = self.bounds()?;
+        if y > max.y {
+            return None;
+        }
 
-    pub fn iter_rows(&self) -> std::slice::Iter<Vec<Tile>> {
-        self.data.iter_rows()
+        Some(
+            self.data
+                .get(&Coordinate { x, y })
+                .copied()
+                .unwrap_or_default(),
+        )
     }
 
-    pub fn dimensions(&self) -> (usize, usize) {
-        self.dimensions
+    pub fn set(&mut self, x: i64, y: i64, t: Tile) {
+        if t == Empty {
+            self.data.remove(&Coordinate { x, y });
+        } else {
+            self.data.insert(Coordinate { x, y }, t);
+        }
     }
 
     /// bounds returns the topmost and bottommost nonempty coordinates
     /// None is returned if the dataset is empty.
     pub fn bounds(&self) -> Option<(Coordinate, Coordinate)> {
-        let mut minc = None;
-        let mut maxc = None;
-        for (y, row) in self.iter_rows().enumerate() {
-            for (x, t) in row.iter().enumerate() {
-                if *t == Empty {
-                    continue;
-                }
-
-                match minc {
-                    None => minc = Some(Coordinate { x, y }),
-                    Some(c) if c.x <= x || c.y <= y => {
-                        minc = Some(Coordinate {
-                            x: min(c.x, x),
-                            y: min(c.y, y),
-                        })
-                    }
-                    _ => (),
-                }
-
-                match maxc {
-                    None => maxc = Some(Coordinate { x, y }),
-                    Some(c) if c.x >= x || c.y >= y => {
-                        maxc = Some(Coordinate {
-                            x: max(c.x, x),
-                            y: max(c.y, y),
-                        })
-                    }
-                    _ => (),
-                }
-            }
-        }
-
-        minc.and_then(|min| maxc.map(|max| (min, max)))
-    }
-
-    pub fn height(&self) -> usize {
-        self.dimensions.1
-    }
-
-    pub fn width(&self) -> usize {
-        self.dimensions.0
+        self.data.bounds()
     }
 }
 
@@ -105,23 +71,7 @@ impl fmt::Display for Tile {
 impl fmt::Display for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // we only print the minimal span of tiles which are not empty
-        let b = self.bounds();
-        if b.is_none() {
-            return Ok(());
-        }
-        let (lower, upper) = b.unwrap();
-
-        for (y, row) in self.data.iter_rows().enumerate() {
-            if y > upper.y || y < lower.y {
-                continue;
-            }
-            for col in &row[lower.x..=upper.x] {
-                write!(f, "{}", col)?;
-            }
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.data)
     }
 }
 
@@ -132,22 +82,7 @@ impl FromStr for Map {
     // and derives a Map
     fn from_str(s: &str) -> Result<Self> {
         let lines: Vec<Vec<Coordinate>> = s.lines().map(parse_line).collect::<Result<_>>()?;
-        let first = lines
-            .first()
-            .and_then(|l| l.first())
-            .ok_or_else(|| anyhow!("expect at least one line"))?;
-        let upper = lines
-            .iter()
-            .flatten()
-            .fold((first.x, first.y), |last, curr| {
-                (max(last.0, curr.x), max(last.1, curr.y))
-            });
-
-        let dimensions = ((upper.0 + 1) as usize, (upper.1 + 1) as usize); // add 1 since coordinates include 0
-
-        let mut m = Self {
-            data: BaseMap::new_dense(dimensions.0, dimensions.1),
-        };
+        let mut m = Self::default();
 
         // finally draw the wall lines on the map
         for line in lines {
@@ -157,11 +92,11 @@ impl FromStr for Map {
                     let rng = if lp.x == tp.x {
                         repeat(lp.x)
                             .zip(min(lp.y, tp.y)..=max(lp.y, tp.y))
-                            .collect::<Vec<(usize, usize)>>()
+                            .collect::<Vec<(i64, i64)>>()
                     } else if lp.y == tp.y {
                         (min(lp.x, tp.x)..=max(lp.x, tp.x))
                             .zip(repeat(lp.y))
-                            .collect::<Vec<(usize, usize)>>()
+                            .collect::<Vec<(i64, i64)>>()
                     } else {
                         bail!(
                             "only vertical or horizontal line drawing is supported, got: {} -> {}",
@@ -171,7 +106,7 @@ impl FromStr for Map {
                     };
 
                     for (x, y) in rng {
-                        *m.get_mut(x, y)? = Rock;
+                        m.set(x, y, Rock);
                     }
                 }
 
@@ -201,18 +136,18 @@ mod test {
         let wall_coords = repeat(2)
             .zip(2..=5)
             .chain((2..=4).zip(repeat(5)))
-            .collect::<HashSet<(usize, usize)>>();
+            .collect::<HashSet<(i64, i64)>>();
 
         for x in 2..=4 {
             for y in 2..=5 {
                 let c = m.get(x, y);
-                assert!(c.is_ok(), "{:?}", c);
+                assert!(c.is_some(), "{:?}", c);
                 let c = c.unwrap();
 
                 if wall_coords.contains(&(x, y)) {
-                    assert_eq!(Rock, *c, "expected rock at ({}, {})", x, y);
+                    assert_eq!(Rock, c, "expected rock at ({}, {})", x, y);
                 } else {
-                    assert_eq!(Empty, *c, "expected empty at ({}, {})", x, y);
+                    assert_eq!(Empty, c, "expected empty at ({}, {})", x, y);
                 }
             }
         }
@@ -237,7 +172,7 @@ mod test {
 
         let mut m: Map = input.parse().expect("should parse");
         // sand falls from (500, 0)
-        *m.get_mut(500, 0).unwrap() = Source;
+        m.set(500, 0, Source);
 
         assert_eq!(
             expected,
@@ -1,21 +1,26 @@
 mod map;
 mod physics;
 
-use anyhow::Result;
+use crate::prelude::*;
 use map::Map;
 use physics::{time_until_full, time_until_source_covered};
 
-pub fn run(input: String) -> Result<()> {
-    let mut m: Map = input.parse()?;
-    let cnt = time_until_full(&mut m, (500, 0));
+pub struct Solution;
 
-    println!("took {} rounds to fill", cnt);
+impl Solver for Solution {
+    type Input = Map;
 
-    println!("========= part 2 =========");
-    let mut m: Map = input.parse()?;
-    let cnt = time_until_source_covered(&mut m, (500, 0));
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-    println!("took {} rounds for sand to cover source", cnt);
+    fn part1(m: &Self::Input) -> Result<Answer> {
+        Ok(time_until_full(&mut m.clone(), (500, 0)).into())
+    }
 
-    Ok(())
+    fn part2(m: &Self::Input) -> Result<Answer> {
+        Ok(time_until_source_covered(&mut m.clone(), (500, 0)).into())
+    }
 }
+
+register!(2022, 14, Solution);
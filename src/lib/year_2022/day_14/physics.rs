@@ -1,48 +1,32 @@
-use std::cmp::max;
+use crate::prelude::*;
 
 use super::map::{Map, Tile::*};
 
-/// resizes to be larger, then draws a long line at bounds.1.1 + 2
-pub fn time_until_source_covered(m: &mut Map, spawn: (usize, usize)) -> usize {
-    let dimens = m.dimensions();
-    let b = m.bounds().unwrap();
-    let (bminx, bmaxx, bmaxy) = (b.0.x, b.1.x, b.1.y);
-
-    let line_width = dimens.1 - spawn.1; // the bottom portion of our 45-45-90 triangle
-    let bmaxx = if bminx < line_width {
-        bmaxx + line_width + line_width - bminx
-    } else {
-        bmaxx + line_width
+/// draws a floor two below the lowest rock, wide enough that sand piling up
+/// from spawn can't fall off its ends, then fills until spawn is covered
+pub fn time_until_source_covered(m: &mut Map, spawn: (i64, i64)) -> usize {
+    let Some((_, max)) = m.bounds() else {
+        return 0;
     };
-    let bminx = max(0, bminx - line_width);
-
-    if spawn.0 > dimens.0 || spawn.1 > dimens.1 || bmaxy + 2 > dimens.1 {
-        let ndimens = (
-            max(bmaxx + 1, spawn.0),
-            max(max(dimens.1, spawn.1), bmaxy + 3),
-        );
-        println!(
-            "resize m from {:?} to {:?} so that it includes spawn ({:?}) and line (y={})",
-            dimens,
-            ndimens,
-            spawn,
-            bmaxy + 2
-        );
-        m.resize(ndimens.0, ndimens.1);
-    }
-
-    println!("drawing line from x={} to x={}", bminx, bmaxx);
-    *m.get_mut(spawn.0, spawn.1).unwrap() = Source;
-    for x in bminx..=bmaxx {
-        *m.get_mut(x, bmaxy + 2).unwrap() = Rock;
-        // println!("({}, {}) = {}", x, bmaxy + 2, m.get(x, bmaxy + 2).unwrap());
+    let floor = max.y + 2;
+
+    // sand piles into a 45-45-90 triangle, so it can't spread further than this
+    let reach = floor - spawn.1;
+    debug!(
+        "drawing floor at y={} from x={} to x={}",
+        floor,
+        spawn.0 - reach,
+        spawn.0 + reach
+    );
+    m.set(spawn.0, spawn.1, Source);
+    for x in spawn.0 - reach..=spawn.0 + reach {
+        m.set(x, floor, Rock);
     }
 
     let mut cnt = 0;
     loop {
-        // println!("\n{}", m);
-        if spawn_sand(m, (spawn.0, spawn.1)).is_none() {
-            println!(
+        if spawn_sand(m, spawn).is_none() {
+            warn!(
                 "could no longer place sand while trying to cover source:\n{}",
                 m
             );
@@ -50,7 +34,7 @@ pub fn time_until_source_covered(m: &mut Map, spawn: (usize, usize)) -> usize {
         }
         cnt += 1;
 
-        if let Ok(Sand) = m.get(spawn.0, spawn.1) {
+        if let Some(Sand) = m.get(spawn.0, spawn.1) {
             break;
         }
     }
@@ -58,20 +42,11 @@ pub fn time_until_source_covered(m: &mut Map, spawn: (usize, usize)) -> usize {
     cnt
 }
 
-pub fn time_until_full(m: &mut Map, spawn: (usize, usize)) -> usize {
-    let dimens = m.dimensions();
-    if spawn.0 > dimens.0 || spawn.1 > dimens.1 {
-        println!("resize m from {:?} to include spawn: {:?}", dimens, spawn);
-        m.resize(max(dimens.0, spawn.0), max(dimens.1, spawn.1));
-    }
-    *m.get_mut(spawn.0, spawn.1).unwrap() = Source;
+pub fn time_until_full(m: &mut Map, spawn: (i64, i64)) -> usize {
+    m.set(spawn.0, spawn.1, Source);
 
     let mut cnt = 0;
-    loop {
-        if spawn_sand(m, (spawn.0, spawn.1)).is_none() {
-            break;
-        }
-        // println!("\n{}", m);
+    while spawn_sand(m, spawn).is_some() {
         cnt += 1;
     }
 
@@ -81,37 +56,30 @@ pub fn time_until_full(m: &mut Map, spawn: (usize, usize)) -> usize {
 /// spawn_sand spawns sand at the passed coordinates and simulates
 /// phyiscs on it until it drops. If it does not settle and instead falls
 /// off the map, None is returned.
-pub fn spawn_sand(m: &mut Map, at: (usize, usize)) -> Option<()> {
+pub fn spawn_sand(m: &mut Map, at: (i64, i64)) -> Option<()> {
     // We won't modify the map until we are sure the sand settles. There's no
     // point since it doesn't affect the result.
     match m.get(at.0, at.1) {
-        Ok(Empty) | Ok(Source) => (),
-        Ok(_) => {
-            println!("cannot spawn at {:?}, already taken", at);
+        Some(Empty) | Some(Source) => (),
+        Some(_) => {
+            trace!("cannot spawn at {:?}, already taken", at);
             return None;
         }
-        _ => (),
+        None => return None,
     }
 
     for (x, y) in [(at.0, at.1 + 1), (at.0 - 1, at.1 + 1), (at.0 + 1, at.1 + 1)] {
         match m.get(x, y) {
-            Ok(Empty) => {
-                // println!("{:?} => ({}, {})", at, x, y);
-                return spawn_sand(m, (x, y));
-            }
-            Err(_) => return None,
-            Ok(_) => (),
+            Some(Empty) => return spawn_sand(m, (x, y)),
+            // below everything, so it falls forever
+            None => return None,
+            Some(_) => (),
         }
     }
 
     // sand settles, no empty spot
-    match m.get_mut(at.0, at.1) {
-        Ok(ref mut t) => {
-            **t = Sand;
-            Some(())
-        }
-        _ => None,
-    }
+    m.set(at.0, at.1, Sand);
+    Some(())
 }
 
 #[cfg(test)]
@@ -125,10 +93,10 @@ mod test {
 
         let mut m: Map = input.parse().expect("should parse");
         // sand falls from (500, 0)
-        *m.get_mut(500, 0).unwrap() = Source;
+        m.set(500, 0, Source);
 
         assert_eq!(Some(()), spawn_sand(&mut m, (500, 0)));
-        assert_eq!(Sand, *m.get(500, 8).unwrap(), "{}", m);
+        assert_eq!(Some(Sand), m.get(500, 8), "{}", m);
     }
 
     #[test]
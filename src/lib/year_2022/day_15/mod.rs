@@ -3,30 +3,33 @@ use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
 
-use crate::prelude::*;
-
-pub fn run(input: String) -> Result<()> {
-    let m: Map = input.parse()?;
-
-    println!(
-        "part 1, row y=2M has {} squares covered by sensors",
-        m.positions_without_beacon(2_000_000)
-    );
-
-    println!("===== part 2 =====");
-    let (x, y) = m
-        .find_distress_signal(0, 0, 4_000_000, 4_000_000)
-        .expect("failed to find signal");
-    println!(
-        "distress beacon @ ({}, {}) with frequency {}",
-        x,
-        y,
-        x * 4000000 + y
-    );
-
-    Ok(())
+use crate::{prelude::*, range_set::RangeSet};
+
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(m: &Self::Input) -> Result<Answer> {
+        Ok(m.positions_without_beacon(2_000_000).into())
+    }
+
+    fn part2(m: &Self::Input) -> Result<Answer> {
+        let (x, y) = m
+            .find_distress_signal(0, 0, 4_000_000, 4_000_000)
+            .ok_or_else(|| anyhow!("failed to find signal"))?;
+        debug!("distress beacon @ ({}, {})", x, y);
+
+        Ok((x * 4000000 + y).into())
+    }
 }
 
+register!(2022, 15, Solution);
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct Sensor {
     x: i64,
@@ -96,7 +99,7 @@ impl FromStr for Sensor {
     }
 }
 
-struct Map {
+pub struct Map {
     dimensions: (i64, i64),
     left: (i64, i64), // left side of drawn graphs, from S or B
     sensors: Vec<Sensor>,
@@ -106,24 +109,25 @@ impl Map {
     /// returns the number of positions on a given line (y=#) for which a beacon
     /// cannot be present.
     pub fn positions_without_beacon(&self, y: i64) -> usize {
-        let mut cnt = 0;
-        let nodes: HashSet<(i64, i64)> = self
+        // each sensor covers a span of the line which narrows the further away it is
+        let mut covered: RangeSet<i64> = self
             .sensors
             .iter()
-            .flat_map(|s| vec![(s.x, s.y), s.beacon])
+            .map(|s| (s, s.range - (y - s.y).abs()))
+            .filter(|(_, half)| *half >= 0)
+            .map(|(s, half)| s.x - half..s.x + half + 1)
             .collect();
 
-        for x in self.left.0..self.dimensions.0 {
-            if nodes.get(&(x, y)).is_some() {
-                continue;
-            }
-
-            if self.sensors.iter().any(|s| s.contains(x, y)) {
-                cnt += 1;
-            }
+        for (x, _) in self
+            .sensors
+            .iter()
+            .flat_map(|s| vec![(s.x, s.y), s.beacon])
+            .filter(|(_, ny)| *ny == y)
+        {
+            covered.remove(x..x + 1);
         }
 
-        cnt
+        covered.total_len() as usize
     }
 
     /// find_distress_signal looks at all the points on the edge of sensors perimeter in the range.
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
-use crate::prelude::*;
+use crate::{prelude::*, search};
 
 #[derive(Clone, Debug, Default)]
 pub struct Graph {
@@ -23,7 +23,6 @@ impl Valve {
 }
 
 impl Graph {
-    #[allow(dead_code)]
     pub fn start(&self) -> Option<&Valve> {
         self.valves.get("AA")
     }
@@ -131,6 +130,20 @@ impl Graph {
     }
 }
 
+// valves are searched by name, with every tunnel taking a minute to walk
+impl search::Graph for Graph {
+    type Node = String;
+
+    fn neighbors(&self, name: &String) -> Vec<(String, u64)> {
+        self.valves
+            .get(name)
+            .into_iter()
+            .flat_map(|v| &v.neighbors)
+            .map(|v| (v.name.clone(), 1))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -9,24 +9,34 @@ mod graph;
 use std::collections::{HashMap, HashSet};
 use std::convert::AsRef;
 use std::fmt;
+use std::rc::Rc;
 
 use graph::{Graph, Valve};
 
-use crate::prelude::*;
+use crate::{prelude::*, search};
 
-pub fn run(input: String) -> Result<()> {
-    let g: Graph = input.parse()?;
+pub struct Solution;
 
-    let mut solver = Simulation::new(&g, 1);
+impl Solver for Solution {
+    type Input = Graph;
 
-    println!("pt1: max flow found: {}", solver.solve_dijkstra());
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-    let mut solver = Simulation::new(&g, 2);
-    println!("pt2: max flow found: {}", solver.solve_dijkstra());
+    fn part1(g: &Self::Input) -> Result<Answer> {
+        let mut solver = Simulation::new(g, 1);
+        Ok(solver.solve_dijkstra().into())
+    }
 
-    Ok(())
+    fn part2(g: &Self::Input) -> Result<Answer> {
+        let mut solver = Simulation::new(g, 2);
+        Ok(solver.solve_dijkstra().into())
+    }
 }
 
+register!(2022, 16, Solution);
+
 #[derive(Clone, Debug)]
 pub enum Action {
     Move {
@@ -81,6 +91,8 @@ pub struct Simulation<'a> {
     /// cumulative flow so far
     max_turns: u32,
     graph: &'a Graph,
+    /// minutes to walk between every pair of valves, shared between clones
+    distances: Rc<HashMap<(String, String), u64>>,
     /// player can spawn up to max_players for time cost = 4
     max_players: usize,
 
@@ -101,8 +113,11 @@ pub struct Simulation<'a> {
 /// annoying Arc semantics.
 impl<'a> Simulation<'a> {
     pub fn new(graph: &'a Graph, max_players: u32) -> Self {
+        let names: Vec<String> = graph.valves.keys().cloned().collect();
+
         Self {
             graph,
+            distances: Rc::new(search::all_pairs(graph, &names)),
             max_turns: 30,
             max_players: max_players as usize,
 
@@ -235,16 +250,19 @@ impl<'a> Simulation<'a> {
         let next_flow = self.cum_flow + self.cum_rate;
 
         for (i, name, pos) in &players {
+            // only step towards valves which are still worth opening
+            let neighbors = pos
+                .neighbors()
+                .iter()
+                .filter(|v| self.closer_to_closed_valve(&pos.name, &v.name));
             let neighbor_keys: Vec<String> = if self.max_players == 1 {
-                pos.neighbors().iter().map(|v| v.name.clone()).collect()
+                neighbors.map(|v| v.name.clone()).collect()
             } else if *i == 0 {
-                pos.neighbors()
-                    .iter()
+                neighbors
                     .map(|v| v.name.clone() + &self.players.get(1).unwrap().pos.name)
                     .collect()
             } else {
-                pos.neighbors()
-                    .iter()
+                neighbors
                     .map(|v| self.players.get(1).unwrap().pos.name.clone() + &v.name)
                     .collect()
             };
@@ -323,6 +341,21 @@ impl<'a> Simulation<'a> {
         results.into_iter().max_by_key(|r| r.cum_flow)
     }
 
+    /// closer_to_closed_valve returns if walking from one valve to the next shortens
+    /// the walk to some valve with flow which isn't open yet
+    fn closer_to_closed_valve(&self, from: &str, to: &str) -> bool {
+        let distance = |a: &str, b: &str| self.distances.get(&(a.to_string(), b.to_string()));
+
+        self.graph
+            .valves
+            .values()
+            .filter(|v| v.rate > 0 && !self.open_valves.contains(&v.name))
+            .any(|v| match (distance(to, &v.name), distance(from, &v.name)) {
+                (Some(next), Some(cur)) => next < cur,
+                _ => false,
+            })
+    }
+
     fn key(&self) -> String {
         self.players
             .iter()
@@ -553,8 +586,6 @@ mod test {
         let graph: Graph = input.parse().unwrap();
         let mut solver = Simulation::new(&graph, 1);
 
-        println!("{}", graph);
-
         assert_eq!(1651, solver.solve_dijkstra());
     }
 
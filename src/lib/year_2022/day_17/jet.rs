@@ -10,6 +10,21 @@ pub enum JetDirection {
 #[derive(Debug, Eq, PartialEq)]
 pub struct JetPattern(Vec<JetDirection>);
 
+impl JetPattern {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// get returns the ith push, repeating the pattern forever.
+    pub fn get(&self, i: usize) -> &JetDirection {
+        &self.0[i % self.0.len()]
+    }
+}
+
 impl IntoIterator for JetPattern {
     type Item = JetDirection;
 
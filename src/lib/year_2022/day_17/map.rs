@@ -1,52 +1,104 @@
-#![allow(dead_code)]
+use std::fmt;
+
+use crate::grid::Grid;
 use crate::prelude::*;
 
+use super::rock::Rock;
+
 /// Map is grid of configurable maximum width with growing height.
-/// Map stores whether a block is set or not.
+/// Map stores whether a block is set or not. y = 0 is the row above the floor.
 #[derive(Debug, Clone)]
 pub struct Map {
-    // Y -> X -> is_set
-    grid: Vec<Vec<bool>>,
-    width: usize,
+    grid: Grid<bool>,
     height: usize,
 }
 
 impl Map {
     pub fn new(width: usize) -> Self {
         Self {
-            grid: vec![],
+            grid: Grid::new(width, 0),
             height: 0,
-            width,
         }
     }
 
     pub fn get(&self, x: usize, y: usize) -> bool {
-        self.grid
-            .get(y)
-            .and_then(|row| row.get(x))
-            .cloned()
-            .unwrap_or_default()
+        self.grid.get(x, y).copied().unwrap_or_default()
     }
 
     pub fn get_mut(&mut self, x: usize, y: usize) -> &mut bool {
-        if y >= self.grid.len() {
-            self.grid.resize(y + 1, vec![false; self.width]);
+        if y >= self.grid.height() {
+            self.grid.resize(self.grid.width(), y + 1);
         }
 
-        self.grid.get_mut(y).unwrap().get_mut(x).unwrap()
+        self.grid.get_mut(x, y).unwrap()
     }
 
     pub fn set(&mut self, x: usize, y: usize) -> Result<()> {
-        if x >= self.width {
+        if x >= self.grid.width() {
             bail!(
                 "({x}, {y}) is out of bounds in Map with width {}",
-                self.width
+                self.grid.width()
             );
         }
 
         *self.get_mut(x, y) = true;
+        self.height = self.height.max(y + 1);
+        Ok(())
+    }
+
+    /// height is the height of the tower: one more than the highest set row.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// fits returns whether rock can be at (x, y) without leaving the map or
+    /// overlapping anything. (x, y) is the rock's bottom left corner.
+    pub fn fits(&self, rock: Rock, x: usize, y: usize) -> bool {
+        x + rock.width() <= self.grid.width()
+            && rock
+                .cells()
+                .iter()
+                .all(|(dx, dy)| !self.get(x + dx, y + dy))
+    }
+
+    /// place sets every cell of rock with its bottom left corner at (x, y).
+    pub fn place(&mut self, rock: Rock, x: usize, y: usize) -> Result<()> {
+        for (dx, dy) in rock.cells() {
+            self.set(x + dx, y + dy)?;
+        }
         Ok(())
     }
+
+    /// profile returns the depth of each column's highest block below the top
+    /// of the tower, up to max_depth. Falling rocks rarely reach far below the
+    /// top, so towers with the same profile behave the same from then on.
+    pub fn profile(&self, max_depth: usize) -> Vec<usize> {
+        (0..self.grid.width())
+            .map(|x| {
+                (0..max_depth.min(self.height))
+                    .find(|depth| self.get(x, self.height - depth - 1))
+                    .unwrap_or(max_depth)
+            })
+            .collect()
+    }
+}
+
+/// Map is drawn like the puzzle's examples, from the top of the tower down to
+/// the floor.
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in (0..self.height).rev() {
+            let row = (0..self.grid.width())
+                .map(|x| match self.get(x, y) {
+                    true => '#',
+                    false => '.',
+                })
+                .collect::<String>();
+            writeln!(f, "|{row}|")?;
+        }
+
+        write!(f, "+{}+", "-".repeat(self.grid.width()))
+    }
 }
 
 #[cfg(test)]
@@ -74,5 +126,25 @@ mod tests {
         assert!(map.set(10, 5).is_err());
         assert!(!map.get(5, 7));
         assert!(!map.get(5, 10));
+        assert_eq!(51, map.height());
+    }
+
+    #[test]
+    fn test_map_place_and_display() {
+        let mut map = Map::new(7);
+        assert_eq!("+-------+", map.to_string());
+
+        map.place(Rock::Minus, 2, 0).unwrap();
+        assert!(!map.fits(Rock::Plus, 1, 0));
+        assert!(map.fits(Rock::Plus, 1, 1));
+        assert!(!map.fits(Rock::Plus, 5, 1));
+        map.place(Rock::Plus, 1, 1).unwrap();
+
+        assert_eq!(4, map.height());
+        assert_eq!(vec![4, 1, 0, 1, 3, 3, 4], map.profile(4));
+        assert_eq!(
+            "|..#....|\n|.###...|\n|..#....|\n|..####.|\n+-------+",
+            map.to_string()
+        );
     }
 }
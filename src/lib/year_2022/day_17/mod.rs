@@ -1,16 +1,156 @@
 mod jet;
 mod map;
+mod rock;
 use jet::*;
+use map::Map;
+use rock::{Rock, ROCKS};
 
+use crate::cycle;
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let _: JetPattern = input.parse()?;
+pub struct Solution;
 
-    Ok(())
+impl Solver for Solution {
+    type Input = JetPattern;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let jets: JetPattern = input.trim().parse()?;
+        ensure!(!jets.is_empty(), "no jets in input");
+        Ok(jets)
+    }
+
+    fn part1(jets: &Self::Input) -> Result<Answer> {
+        let mut chamber = Chamber::new(jets);
+        for _ in 0..2022 {
+            chamber.drop_rock()?;
+        }
+        trace!("tower after 2022 rocks:\n{}", chamber.map);
+
+        Ok(chamber.map.height().into())
+    }
+
+    fn part2(jets: &Self::Input) -> Result<Answer> {
+        Ok(tower_height(jets, 1_000_000_000_000)?.into())
+    }
+}
+
+register!(2022, 17, Solution);
+
+const WIDTH: usize = 7;
+
+// PROFILE_DEPTH is how far below the top of the tower the surface is compared
+// when looking for a repeated state
+const PROFILE_DEPTH: usize = 64;
+
+// Chamber is the tower being built and where we are in the jet pattern and the
+// order rocks fall in.
+struct Chamber<'a> {
+    map: Map,
+    jets: &'a JetPattern,
+    jet: usize,
+    rocks: usize,
+}
+
+// State is everything which decides how the rest of the tower is built.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct State {
+    rock: usize,
+    jet: usize,
+    profile: Vec<usize>,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(jets: &'a JetPattern) -> Self {
+        Self {
+            map: Map::new(WIDTH),
+            jets,
+            jet: 0,
+            rocks: 0,
+        }
+    }
+
+    // drop_rock drops the next rock until it settles. Rocks appear two from the
+    // left wall and three rows above the tower, then alternate between being
+    // pushed by a jet and falling one row.
+    fn drop_rock(&mut self) -> Result<()> {
+        let rock: Rock = ROCKS[self.rocks % ROCKS.len()];
+        self.rocks += 1;
+        let (mut x, mut y): (usize, usize) = (2, self.map.height() + 3);
+
+        loop {
+            let pushed = match self.jets.get(self.jet) {
+                JetDirection::Left => x.checked_sub(1),
+                JetDirection::Right => Some(x + 1),
+            };
+            self.jet = (self.jet + 1) % self.jets.len();
+            if let Some(px) = pushed.filter(|px| self.map.fits(rock, *px, y)) {
+                x = px;
+            }
+
+            match y.checked_sub(1).filter(|ny| self.map.fits(rock, x, *ny)) {
+                Some(ny) => y = ny,
+                None => return self.map.place(rock, x, y),
+            }
+        }
+    }
+
+    fn state(&self) -> State {
+        State {
+            rock: self.rocks % ROCKS.len(),
+            jet: self.jet,
+            profile: self.map.profile(PROFILE_DEPTH),
+        }
+    }
+}
+
+// tower_height returns the height of the tower after rocks have fallen. Once a
+// state repeats, whole cycles of rocks are skipped by adding the height each adds.
+fn tower_height(jets: &JetPattern, rocks: usize) -> Result<usize> {
+    let height = cycle::extrapolate(
+        &mut Chamber::new(jets),
+        rocks,
+        Chamber::drop_rock,
+        Chamber::state,
+        |c| c.map.height() as i64,
+    )?;
+
+    Ok(height as usize)
 }
 
 #[cfg(test)]
 mod tests {
-    const _EXAMPLE_ONE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+    use super::*;
+
+    const EXAMPLE_ONE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn test_first_rocks() {
+        let jets = Solution::parse(EXAMPLE_ONE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        for _ in 0..3 {
+            chamber.drop_rock().unwrap();
+        }
+
+        assert_eq!(
+            "|..#....|\n|..#....|\n|####...|\n|..###..|\n|...#...|\n|..####.|\n+-------+",
+            chamber.map.to_string()
+        );
+    }
+
+    #[test]
+    fn test_cycles_match_simulation() {
+        let jets = Solution::parse(EXAMPLE_ONE).unwrap();
+
+        for rocks in [10, 2022, 5000] {
+            let mut chamber = Chamber::new(&jets);
+            for _ in 0..rocks {
+                chamber.drop_rock().unwrap();
+            }
+            assert_eq!(
+                chamber.map.height(),
+                tower_height(&jets, rocks).unwrap(),
+                "{rocks} rocks"
+            );
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use std::fmt;
+
+// ROCKS are the shapes which fall, in the order they fall
+pub const ROCKS: [Rock; 5] = [
+    Rock::Minus,
+    Rock::Plus,
+    Rock::Corner,
+    Rock::Pipe,
+    Rock::Square,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rock {
+    Minus,
+    Plus,
+    // a backwards L
+    Corner,
+    Pipe,
+    Square,
+}
+
+impl Rock {
+    // cells returns the (x, y) offsets of the rock's cells from its bottom left
+    // corner, with y increasing upwards
+    pub fn cells(&self) -> &'static [(usize, usize)] {
+        match self {
+            Rock::Minus => &[(0, 0), (1, 0), (2, 0), (3, 0)],
+            Rock::Plus => &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            Rock::Corner => &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+            Rock::Pipe => &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            Rock::Square => &[(0, 0), (1, 0), (0, 1), (1, 1)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells()
+            .iter()
+            .map(|(x, _)| x + 1)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells()
+            .iter()
+            .map(|(_, y)| y + 1)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Rock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = (0..self.height())
+            .rev()
+            .map(|y| {
+                (0..self.width())
+                    .map(|x| match self.cells().contains(&(x, y)) {
+                        true => '#',
+                        false => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rock_display() {
+        assert_eq!("####", Rock::Minus.to_string());
+        assert_eq!(".#.\n###\n.#.", Rock::Plus.to_string());
+        assert_eq!("..#\n..#\n###", Rock::Corner.to_string());
+        assert_eq!("#\n#\n#\n#", Rock::Pipe.to_string());
+        assert_eq!("##\n##", Rock::Square.to_string());
+    }
+}
@@ -1,3 +1,7 @@
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
 pub mod day_05;
 pub mod day_06;
 pub mod day_07;
@@ -2,21 +2,26 @@ use crate::prelude::*;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
-pub fn run(input: String) -> Result<()> {
-    println!("===== part 1 ======");
-    println!(
-        "calibration value for provided input: {:?}",
-        solve_pt1(&input)
-    );
-
-    println!("===== part 2 ======");
-    println!(
-        "calibration value for provided input: {:?}",
-        solve_pt2(&input)
-    );
-    Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(solve_pt1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(solve_pt2(input)?.into())
+    }
 }
 
+register!(2023, 1, Solution);
+
 // testable entrypoint
 fn solve_pt1(input: &str) -> Result<i64> {
     input
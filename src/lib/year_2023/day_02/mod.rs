@@ -5,26 +5,34 @@ use std::collections::HashMap;
 
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let games: Games = input.parse()?;
-    let rules: GameRules = [
-        ("red".to_string(), 12),
-        ("green".to_string(), 13),
-        ("blue".to_string(), 14),
-    ]
-    .into_iter()
-    .collect();
-
-    println!(
-        "total playable game sum: {}",
-        playable_game_sum(&games, &rules)
-    );
-
-    println!("game power sum: {}", game_power_sum(&games));
-
-    Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Games;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(games: &Self::Input) -> Result<Answer> {
+        let rules: GameRules = [
+            ("red".to_string(), 12),
+            ("green".to_string(), 13),
+            ("blue".to_string(), 14),
+        ]
+        .into_iter()
+        .collect();
+
+        Ok(playable_game_sum(games, &rules).into())
+    }
+
+    fn part2(games: &Self::Input) -> Result<Answer> {
+        Ok(game_power_sum(games).into())
+    }
 }
 
+register!(2023, 2, Solution);
+
 type GameRules = HashMap<CubeKind, usize>;
 
 // sum of all playable game IDs with a set of rules.
@@ -2,13 +2,26 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{neighbor_map::*, prelude::*};
 
-pub fn run(input: String) -> Result<()> {
-    let map = parse_map(&input)?;
-    println!("sum of part numbers: {}", map_part_number_sum(&map)?);
-    println!("sum of gear ratios: {}", map_gear_ratio_sum(&map)?);
-    Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = Map<Tile>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        Ok(map_part_number_sum(map)?.into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        Ok(map_gear_ratio_sum(map)?.into())
+    }
 }
 
+register!(2023, 3, Solution);
+
 fn map_part_number_sum(m: &Map<Tile>) -> Result<u64> {
     m.part_numbers().map(|parts| parts.into_iter().sum())
 }
@@ -27,7 +40,6 @@ fn map_gear_ratio_sum(m: &Map<Tile>) -> Result<u64> {
         let numbers = n
             .neighbors()
             .iter()
-            .map(|n| n.borrow())
             .filter_map(|nd| match nd.value() {
                 Tile::Number { start, number, .. } => Some((*start, *number)),
                 _ => None,
@@ -76,7 +88,7 @@ impl std::fmt::Display for Tile {
 // on every line. Whitespace in input is removed.
 fn parse_map(input: &str) -> Result<Map<Tile>> {
     let input: String = input.chars().filter(|c| *c != ' ' && *c != '\t').collect();
-    let m = Map::new(input.lines().next().unwrap().len(), input.lines().count());
+    let mut m = Map::new(input.lines().next().unwrap().len(), input.lines().count());
 
     let mut lines = vec![];
     for (y, line) in input.lines().rev().enumerate() {
@@ -106,8 +118,7 @@ fn parse_map(input: &str) -> Result<Map<Tile>> {
     // now we can set map contents
     for (y, row) in lines.into_iter().enumerate() {
         for (x, tile) in row.into_iter().enumerate() {
-            let mut t = m.get_mut(x, y).unwrap();
-            t.set(tile);
+            *m.get_mut(x, y)? = tile;
         }
     }
 
@@ -135,7 +146,7 @@ impl PartNumbers for Map<Tile> {
                 continue;
             }
 
-            if t.neighbors().iter().any(|n| n.borrow().value().is_symbol()) {
+            if t.neighbors().iter().any(|n| n.value().is_symbol()) {
                 seen.insert(*start);
                 result.push(*number);
             }
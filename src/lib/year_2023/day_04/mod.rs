@@ -1,18 +1,28 @@
 use crate::prelude::*;
 use std::{collections::HashSet, str::FromStr};
 
-pub fn run(input: String) -> Result<()> {
-    let pile: Pile = input.parse()?;
+pub struct Solution;
 
-    println!("total score for pile: {}", pile.score());
+impl Solver for Solution {
+    type Input = Pile;
 
-    println!("total cards for pile: {}", pile.total_scorecards());
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(pile: &Self::Input) -> Result<Answer> {
+        Ok(pile.score().into())
+    }
 
-    Ok(())
+    fn part2(pile: &Self::Input) -> Result<Answer> {
+        Ok(pile.total_scorecards().into())
+    }
 }
 
+register!(2023, 4, Solution);
+
 #[derive(Debug)]
-struct Pile(Vec<ScratchCard>);
+pub struct Pile(Vec<ScratchCard>);
 
 impl Pile {
     // for the part 1 scoring mechanics
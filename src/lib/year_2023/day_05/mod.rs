@@ -1,33 +1,34 @@
 mod parse;
 
-use crate::prelude::*;
-#[cfg(not(test))]
-use indicatif::{ProgressBar, ProgressStyle};
+use crate::{
+    prelude::*,
+    range_set::{split, RangeSet},
+};
 use parse::Almanac;
-use std::ops::Range;
-#[cfg(not(test))]
-use std::sync::Arc;
-use tokio::{runtime::Runtime, task::JoinSet};
 
-pub fn run(input: String) -> Result<()> {
-    let alm: Almanac = input.parse()?;
+pub struct Solution;
 
-    println!(
-        "minimum location for starter seeds: {}",
-        alm.lowest_location()
-    );
+impl Solver for Solution {
+    type Input = Almanac;
 
-    let sums = alm.total_seed_range_width();
-    println!("total range width: {sums}");
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(alm: &Self::Input) -> Result<Answer> {
+        Ok(alm.lowest_location().into())
+    }
 
-    println!(
-        "minimum seed using started seed ranges: {}",
-        alm.lowest_location_seed_range()
-    );
+    fn part2(alm: &Self::Input) -> Result<Answer> {
+        let sums = alm.total_seed_range_width()?;
+        debug!("total range width: {sums}");
 
-    Ok(())
+        Ok(alm.lowest_location_seed_range()?.into())
+    }
 }
 
+register!(2023, 5, Solution);
+
 impl Almanac {
     // repeatedly maps to retrieve the header value for a seed
     fn header_mapping_for_seed(&self, target_header: &str, mut seed: u64) -> u64 {
@@ -42,7 +43,7 @@ impl Almanac {
                     let old_seed = seed;
                     seed = (seed as i64 + offset) as u64;
                     if seed == 0 {
-                        println!("{old_seed} became zero when mapping over src range {src:?} {header}-to-{target} w/ offset {offset}");
+                        debug!("{old_seed} became zero when mapping over src range {src:?} {header}-to-{target} w/ offset {offset}");
                     }
                     break;
                 }
@@ -54,6 +55,42 @@ impl Almanac {
         seed
     }
 
+    // maps a whole set of seeds at once to retrieve the header values. Each range
+    // is split at the edges of the mappings, so every piece moves by one offset.
+    fn header_mapping_for_seeds(
+        &self,
+        target_header: &str,
+        mut seeds: RangeSet<u64>,
+    ) -> RangeSet<u64> {
+        let mut header = "seed";
+        while header != target_header {
+            let target = self.headers.get(header).expect("unknown header");
+            let mapping_key = (header.to_string(), target.to_string());
+            let ranges = self.lookup.get(&mapping_key).expect("unknown mapping key");
+            let edges = ranges
+                .iter()
+                .flat_map(|(src, _)| [src.start, src.end])
+                .collect::<Vec<_>>();
+
+            seeds = seeds
+                .iter()
+                .flat_map(|rng| split(rng.clone(), edges.iter().copied()))
+                .map(
+                    |piece| match ranges.iter().find(|(src, _)| src.contains(&piece.start)) {
+                        Some((_, offset)) => {
+                            (piece.start as i64 + offset) as u64..(piece.end as i64 + offset) as u64
+                        }
+                        None => piece,
+                    },
+                )
+                .collect();
+
+            header = target;
+        }
+
+        seeds
+    }
+
     fn lowest_location(&self) -> u64 {
         self.seeds
             .iter()
@@ -65,103 +102,36 @@ impl Almanac {
     // Lowest location for seed range... evolved.
     //
     // I originally had a bug where I calculated the seed ranges as start/end pairs instead of start/len
-    // pairs. it doubled the input set, which took over an hour to calculate.
-    //
-    // I fixed that later but I added parallelization before that. I kept the parallelization,
-    // which is great since it's still needed. It takes the runtime down from 30m to a few minutes.
+    // pairs. it doubled the input set, which took over an hour to calculate. Even fixed, mapping
+    // seed by seed in parallel took a few minutes.
     //
-    // Divide and conquer: the seed ranges are split into ranges of size 250MM, then processed in parallel
-    // to find a minimum.
-    fn lowest_location_seed_range(&self) -> u64 {
-        #[cfg(not(test))]
-        let prog = Arc::new(
-            ProgressBar::new(self.total_seed_range_width()).with_style(
-                ProgressStyle::with_template(
-                    "[{elapsed}] {wide_bar:00.cyan/blue} {human_pos:>7}/{human_len:7} [ETA {eta}]",
-                )
-                .unwrap(),
-            ),
-        );
-
-        let rt = Runtime::new().unwrap();
-        let mut joins = JoinSet::new();
-
-        let ranges = self
-            .seed_ranges()
-            .flat_map(|rng| split_range_until_size(rng, 250_000_000))
-            .collect::<Vec<Range<u64>>>();
-
-        for rng in ranges {
-            #[cfg(not(test))]
-            let prog = prog.clone();
-            let alm = self.clone();
-
-            joins.spawn_blocking_on(
-                move || {
-                    let mut min = u64::MAX;
-                    for seed in rng {
-                        let loc = alm.header_mapping_for_seed("location", seed);
-                        if min > loc {
-                            min = loc;
-                        }
-
-                        #[cfg(not(test))]
-                        prog.inc(1);
-                    }
-                    min
-                },
-                rt.handle(),
-            );
-        }
-
-        let mut min = u64::MAX;
-        rt.block_on(async {
-            while let Some(Ok(n)) = joins.join_next().await {
-                println!("task exited");
-                if min > n {
-                    min = n;
-                }
-            }
-        });
-        min
-    }
-
-    // Returns pt2 seed ranges for easy iteration.
-    fn seed_ranges(&self) -> impl Iterator<Item = Range<u64>> {
-        let mut ranges = vec![];
-        for i in (0..self.seeds.len()).step_by(2) {
-            let start = *self.seeds.get(i).unwrap();
-            ranges.push(start..(start + *self.seeds.get(i + 1).unwrap()));
-        }
-
-        ranges.into_iter()
+    // Mapping the seed ranges as ranges only ever touches the edges of the mappings, so
+    // the lowest location is the start of the first location range.
+    fn lowest_location_seed_range(&self) -> Result<u64> {
+        self.header_mapping_for_seeds("location", self.seed_ranges()?)
+            .min()
+            .context("no seed ranges to find a location for")
     }
 
-    // For displaying the progress bar we calculate the total number of seeds
-    // to evlauate.
-    fn total_seed_range_width(&self) -> u64 {
-        self.seed_ranges().map(|rng| rng.end - rng.start).sum()
-    }
-}
+    // Returns pt2 seed ranges, the seeds read as start/len pairs.
+    fn seed_ranges(&self) -> Result<RangeSet<u64>> {
+        ensure!(
+            self.seeds.len() % 2 == 0,
+            "expected seed start/len pairs, got an odd number of seeds: {}",
+            self.seeds.len()
+        );
 
-// Splits a range into subranges that are at most size. It does this by trimming
-// off the end into pieces of size until the original rnage is at most size.
-fn split_range_until_size(mut rng: Range<u64>, size: usize) -> Vec<Range<u64>> {
-    if rng.end - rng.start < size as u64 {
-        return vec![rng];
+        Ok(self
+            .seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect())
     }
 
-    let mut pieces = Vec::with_capacity((rng.end - rng.start) as usize / size);
-
-    while rng.end - rng.start > size as u64 {
-        let newend = rng.end - size as u64;
-        pieces.push(newend..rng.end);
-        rng = rng.start..newend;
+    // The total number of seeds to evaluate in pt2.
+    fn total_seed_range_width(&self) -> Result<u64> {
+        Ok(self.seed_ranges()?.total_len())
     }
-
-    pieces.push(rng);
-
-    pieces
 }
 
 #[allow(dead_code)]
@@ -202,7 +172,6 @@ humidity-to-location map:
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
 
     #[test]
     fn test_example_mapping() {
@@ -215,24 +184,33 @@ mod tests {
     fn test_example_range_seeds() {
         let a: Almanac = EXAMPLE_1.parse().unwrap();
 
-        assert_eq!(46, a.lowest_location_seed_range());
+        assert_eq!(46, a.lowest_location_seed_range().unwrap());
     }
 
     #[test]
-    fn test_split_range() {
-        let testrange = 0..25;
-        let tofind = testrange.clone().collect::<HashSet<_>>();
-
-        for size in 1..25 {
-            let mut found = tofind.clone();
-            let ranges = split_range_until_size(testrange.clone(), size);
-            for rng in ranges {
-                for i in rng {
-                    assert!(found.remove(&i));
-                }
-            }
+    fn test_seed_ranges_invalid() {
+        let mut a: Almanac = EXAMPLE_1.parse().unwrap();
+        a.seeds.pop();
+        assert!(a.lowest_location_seed_range().is_err());
 
-            assert!(found.is_empty(), "with size={size}, found had: {:?}", found);
-        }
+        a.seeds.clear();
+        assert!(a.lowest_location_seed_range().is_err());
+    }
+
+    #[test]
+    fn test_range_mapping() {
+        let a: Almanac = EXAMPLE_1.parse().unwrap();
+
+        let seeds = a.seed_ranges().unwrap();
+        let locations = a.header_mapping_for_seeds("location", seeds.clone());
+        let expected: RangeSet<u64> = seeds
+            .iter()
+            .flat_map(|rng| rng.clone())
+            .map(|s| {
+                let loc = a.header_mapping_for_seed("location", s);
+                loc..loc + 1
+            })
+            .collect();
+        assert_eq!(expected, locations);
     }
 }
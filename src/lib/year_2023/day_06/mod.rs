@@ -3,35 +3,44 @@ use std::str::FromStr;
 
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let r: Records = input.parse()?;
+pub struct Solution;
 
-    let product_winning_buttons: u64 = r
-        .iter()
-        .map(|(record_dur, record_dist)| {
-            simulation(*record_dur)
-                .filter(|(_, dist)| dist > &&record_dist)
-                .count() as u64
-        })
-        .product();
+impl Solver for Solution {
+    type Input = (Records, MegaRecord);
 
-    println!("product of winning buttons: {product_winning_buttons}");
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok((input.parse()?, input.parse()?))
+    }
 
-    let mega: MegaRecord = input.parse()?;
-    let product_winning_buttons: u64 = simulation(mega.0)
-        .filter(|(_, dist)| dist > &mega.1)
-        .count() as u64;
+    fn part1((r, _): &Self::Input) -> Result<Answer> {
+        let product_winning_buttons: u64 = r
+            .iter()
+            .map(|(record_dur, record_dist)| {
+                simulation(*record_dur)
+                    .filter(|(_, dist)| dist > record_dist)
+                    .count() as u64
+            })
+            .product();
+
+        Ok(product_winning_buttons.into())
+    }
 
-    println!("product of megarecord: {product_winning_buttons}");
+    fn part2((_, mega): &Self::Input) -> Result<Answer> {
+        let product_winning_buttons: u64 = simulation(mega.0)
+            .filter(|(_, dist)| dist > &mega.1)
+            .count() as u64;
 
-    Ok(())
+        Ok(product_winning_buttons.into())
+    }
 }
 
+register!(2023, 6, Solution);
+
 type Duration = u64;
 type Distance = u64;
 
 #[derive(Debug, Clone)]
-struct Records(HashMap<Duration, Distance>);
+pub struct Records(HashMap<Duration, Distance>);
 
 impl FromStr for Records {
     type Err = Error;
@@ -54,7 +63,7 @@ impl FromStr for Records {
 }
 
 #[derive(Debug)]
-struct MegaRecord(Duration, Distance);
+pub struct MegaRecord(Duration, Distance);
 
 impl FromStr for MegaRecord {
     type Err = Error;
@@ -3,19 +3,27 @@ use cards::*;
 
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let bids = parse_input(&input)?;
+pub struct Solution;
 
-    let total = total_winnings(bids);
-    println!("total winnings: {total}");
+impl Solver for Solution {
+    type Input = String;
 
-    let bids = parse_input_jacks_wild(&input)?;
-    let total = total_winnings(bids);
-    println!("total winnings with jacks wild: {total}");
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        Ok(total_winnings(parse_input(input)?).into())
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok(total_winnings(parse_input_jacks_wild(input)?).into())
+    }
 }
 
+register!(2023, 7, Solution);
+
 fn parse_input(input: &str) -> Result<Vec<(Hand, u64)>> {
     input
         .lines()
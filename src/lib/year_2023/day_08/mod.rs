@@ -1,31 +1,48 @@
 use std::mem;
 
+use crate::cycle;
 use crate::prelude::*;
 mod parse;
+pub use parse::parse_dirs;
 use parse::*;
 
-pub fn run(input: String) -> Result<()> {
-    let mut lines = input.lines();
-    let mut dirs: Directions = lines.next().unwrap().parse()?;
-    let _ = lines.next();
-    let map: Map = lines.collect::<Vec<_>>().join("\n").parse()?;
-
-    let cnt = traverse(&map, &mut dirs, "AAA", "ZZZ")?;
-    println!("steps to ZZZ: {cnt}");
-
-    let cnt = traverse_parallel(
-        &map,
-        &mut dirs,
-        map.keys()
-            .filter(|n| n.ends_with('A'))
-            .map(|s| s.as_str())
-            .collect(),
-    )?;
-    println!("steps for all nodes to end with Z: {cnt}");
-
-    Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = (String, Map);
+
+    // Directions are an endless iterator, so we keep the raw line around and
+    // build a fresh one for each part.
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines();
+        let dirs = lines.next().ok_or_else(|| anyhow!("missing directions"))?;
+        dirs.parse::<Directions>()?;
+        let _ = lines.next();
+        let map: Map = lines.collect::<Vec<_>>().join("\n").parse()?;
+
+        Ok((dirs.to_string(), map))
+    }
+
+    fn part1((dirs, map): &Self::Input) -> Result<Answer> {
+        Ok(traverse(map, &mut dirs.parse()?, "AAA", "ZZZ")?.into())
+    }
+
+    fn part2((dirs, map): &Self::Input) -> Result<Answer> {
+        let cnt = traverse_parallel(
+            map,
+            &parse_dirs(dirs)?,
+            map.keys()
+                .filter(|n| n.ends_with('A'))
+                .map(|s| s.as_str())
+                .collect(),
+        )?;
+
+        Ok(cnt.into())
+    }
 }
 
+register!(2023, 8, Solution);
+
 // traverses the map, returning the total number of
 // nodes visited to get from start to end
 pub fn traverse(map: &Map, dirs: &mut Directions, start: &str, end: &str) -> Result<usize> {
@@ -45,8 +62,7 @@ pub fn traverse(map: &Map, dirs: &mut Directions, start: &str, end: &str) -> Res
 }
 
 // brute force traverses the map from all start points in parallel until all
-// nodes end with a Z. It doesn't terminate.
-#[allow(dead_code)]
+// nodes end with a Z. Correct, but it won't finish on real inputs.
 pub fn traverse_parallel_dumb(map: &Map, dirs: &mut Directions, start: Vec<&str>) -> Result<usize> {
     let mut cnt = 0;
     let mut curs = start.clone();
@@ -55,10 +71,11 @@ pub fn traverse_parallel_dumb(map: &Map, dirs: &mut Directions, start: Vec<&str>
 
     while !done {
         done = true;
+        let dir = dirs.next().unwrap();
         // do one tick, tracking if any node does not end with Z
         for cur in &curs {
             let fork = map.get(*cur).ok_or_else(|| anyhow!("invalid node {cur}"))?;
-            let cur: &str = match dirs.next().unwrap() {
+            let cur: &str = match dir {
                 Dir::Right => fork.right.as_ref(),
                 Dir::Left => fork.left.as_ref(), // wish I could borrow?
             };
@@ -74,41 +91,151 @@ pub fn traverse_parallel_dumb(map: &Map, dirs: &mut Directions, start: Vec<&str>
     Ok(cnt)
 }
 
-// traverses the map from all start points in parallel until all
-// nodes end with a Z. Cheats by taking the LCM of all start nodes
-// cycle times, since cycles end on ZZZ
-pub fn traverse_parallel(map: &Map, dirs: &mut Directions, start: Vec<&str>) -> Result<usize> {
-    let mut cnt = 0;
-    let mut curs = start.clone();
-    let mut next = vec![""; curs.len()];
-    let mut cycles = vec![0; curs.len()];
+// traverses the map from all start points in parallel until all nodes end
+// with a Z. Each start's walk repeats once it's back at a node at the same point
+// in the directions. Before every walk is in its cycle, steps are checked one by
+// one. After, if the Zs in each cycle are exactly the multiples of some period,
+// it's on a Z every period steps, and the answer is the first multiple of the
+// periods' LCM. Inputs where that doesn't hold are refused.
+pub fn traverse_parallel(map: &Map, dirs: &[Dir], start: Vec<&str>) -> Result<usize> {
+    ensure!(!dirs.is_empty(), "no directions");
+    for (node, fork) in map.iter() {
+        for to in [&fork.left, &fork.right] {
+            ensure!(map.contains_key(to), "{node} leads to invalid node {to}");
+        }
+    }
 
-    while cycles.iter().any(|c| *c == 0) {
-        let dir = dirs.next().unwrap();
-        cnt += 1;
+    // (node, index into dirs) after each step
+    let step = |(node, i): &(&str, usize)| {
+        let fork = &map[*node];
+        let next: &str = match dirs[*i] {
+            Dir::Right => fork.right.as_ref(),
+            Dir::Left => fork.left.as_ref(),
+        };
+        (next, (i + 1) % dirs.len())
+    };
 
-        // do one tick, tracking if any node does not end with Z
-        for (i, cur) in curs.iter().enumerate() {
-            if cycles[i] != 0 {
-                continue;
-            }
-            let fork = map.get(*cur).ok_or_else(|| anyhow!("invalid node {cur}"))?;
-            let cur: &str = match dir {
-                Dir::Right => fork.right.as_ref(),
-                Dir::Left => fork.left.as_ref(),
-            };
+    let mut cycles = Vec::with_capacity(start.len());
+    for s in &start {
+        ensure!(map.contains_key(*s), "invalid node {s}");
+        cycles.push(cycle::brent((*s, 0), step));
+    }
 
-            if cur.ends_with('Z') {
-                cycles[i] = cnt;
-            }
+    // until every walk is in its cycle the Zs don't repeat, so those steps are
+    // checked one by one
+    let latest = cycles.iter().map(|c| c.start).max().unwrap_or_default();
+    let mut states = start.iter().map(|s| (*s, 0)).collect::<Vec<_>>();
+    for t in 0..latest {
+        if states.iter().all(|(n, _)| n.ends_with('Z')) {
+            debug!("every walk is on a Z at step {t}, before they're all in their cycles");
+            return Ok(t);
+        }
+        states.iter_mut().for_each(|s| *s = step(s));
+    }
 
-            next[i] = cur;
+    let mut periods = Vec::with_capacity(start.len());
+    for (s, c) in start.iter().zip(&cycles) {
+        // the steps within one pass of the cycle which end on a Z
+        let mut state = (*s, 0);
+        let mut ends = vec![];
+        for t in 0..c.start + c.len {
+            if t >= c.start && state.0.ends_with('Z') {
+                ends.push(t);
+            }
+            state = step(&state);
         }
 
-        mem::swap(&mut next, &mut curs);
+        // it's on a Z every period steps if those are the only ones in the cycle
+        let period = ends.iter().fold(c.len, |acc, t| num::integer::gcd(acc, *t));
+        let every = (c.start..c.start + c.len)
+            .filter(|t| t % period == 0)
+            .collect::<Vec<_>>();
+        ensure!(
+            !ends.is_empty() && ends == every,
+            "{s} is on a Z at steps {ends:?} of its {} step cycle starting at step {}, not every {period} steps",
+            c.len,
+            c.start
+        );
+
+        debug!("{s} is on a Z every {period} steps from step {}", c.start);
+        periods.push(period);
     }
 
-    println!("cycles: {cycles:?}");
+    let cnt = periods
+        .into_iter()
+        .reduce(num::integer::lcm)
+        .ok_or_else(|| anyhow!("no start nodes"))?;
+
+    // the first time they line up once they're all in their cycles
+    Ok(latest.div_ceil(cnt) * cnt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_PARALLEL: &str = "LR
 
-    Ok(cycles.into_iter().reduce(num::integer::lcm).unwrap())
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_traverse_parallel_strategies() {
+        let (dirs, map) = Solution::parse(EXAMPLE_PARALLEL).unwrap();
+
+        let cnt = traverse_parallel_dumb(&map, &mut dirs.parse().unwrap(), vec!["11A", "22A"]);
+        assert_eq!(6, cnt.unwrap());
+
+        let cnt = traverse_parallel(&map, &parse_dirs(&dirs).unwrap(), vec!["11A", "22A"]);
+        assert_eq!(6, cnt.unwrap());
+    }
+
+    #[test]
+    fn test_traverse_parallel_refuses_uneven_cycles() {
+        // 11A reaches 11Z after one step, but then every two steps
+        let (dirs, map) = Solution::parse(
+            "L\n\n11A = (11Z, XXX)\n11Z = (11B, XXX)\n11B = (11Z, XXX)\nXXX = (XXX, XXX)",
+        )
+        .unwrap();
+
+        let err = traverse_parallel(&map, &parse_dirs(&dirs).unwrap(), vec!["11A"]);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_traverse_parallel_tail() {
+        // 11A is on a Z at step 1, before its cycle of 12Z and 11C starts at step 2
+        let (dirs, map) = Solution::parse(
+            "L\n\n11A = (11Z, XXX)\n11Z = (12Z, XXX)\n12Z = (11C, XXX)\n11C = (12Z, XXX)\nXXX = (XXX, XXX)",
+        )
+        .unwrap();
+
+        let cnt = traverse_parallel_dumb(&map, &mut dirs.parse().unwrap(), vec!["11A"]);
+        assert_eq!(1, cnt.unwrap());
+        let cnt = traverse_parallel(&map, &parse_dirs(&dirs).unwrap(), vec!["11A"]);
+        assert_eq!(1, cnt.unwrap());
+    }
+
+    #[test]
+    fn test_traverse_parallel_starting_on_z() {
+        // 11Z is only on a Z at the start of its cycle, every three steps, and
+        // 22A is on one every two steps from step 2
+        let (dirs, map) = Solution::parse(
+            "L\n\n11Z = (11B, XXX)\n11B = (11C, XXX)\n11C = (11Z, XXX)\n22A = (22B, XXX)\n22B = (22Z, XXX)\n22Z = (22B, XXX)\nXXX = (XXX, XXX)",
+        )
+        .unwrap();
+
+        let cnt = traverse_parallel_dumb(&map, &mut dirs.parse().unwrap(), vec!["11Z", "22A"]);
+        assert_eq!(6, cnt.unwrap());
+        let cnt = traverse_parallel(&map, &parse_dirs(&dirs).unwrap(), vec!["11Z", "22A"]);
+        assert_eq!(6, cnt.unwrap());
+        let cnt = traverse_parallel(&map, &parse_dirs(&dirs).unwrap(), vec!["11Z"]);
+        assert_eq!(0, cnt.unwrap());
+    }
 }
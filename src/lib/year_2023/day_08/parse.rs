@@ -36,17 +36,18 @@ impl FromStr for Directions {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        Ok(Self(Box::new(
-            s.split("")
-                .filter(|s| *s != "\n" && *s != " " && !s.is_empty())
-                .map(FromStr::from_str)
-                .collect::<Result<Vec<_>>>()?
-                .into_iter()
-                .cycle(),
-        )))
+        Ok(Self(Box::new(parse_dirs(s)?.into_iter().cycle())))
     }
 }
 
+// parse_dirs parses one pass of the directions, e.g. LLR
+pub fn parse_dirs(s: &str) -> Result<Vec<Dir>> {
+    s.split("")
+        .filter(|s| *s != "\n" && *s != " " && !s.is_empty())
+        .map(FromStr::from_str)
+        .collect()
+}
+
 impl FromStr for Map {
     type Err = Error;
 
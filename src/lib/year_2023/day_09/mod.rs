@@ -1,16 +1,25 @@
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let lines = parse(&input)?;
-    let sum = extrapolate_all_sum(&lines);
-    println!("total input extrapolated sum: {sum}");
+pub struct Solution;
 
-    let sum = extrapolate_backwards_sum(&lines);
-    println!("total input extrapolated backwards sum: {sum}");
+impl Solver for Solution {
+    type Input = Vec<Vec<i64>>;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Answer> {
+        Ok(extrapolate_all_sum(lines).into())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Answer> {
+        Ok(extrapolate_backwards_sum(lines).into())
+    }
 }
 
+register!(2023, 9, Solution);
+
 fn parse(input: &str) -> Result<Vec<Vec<i64>>> {
     input
         .lines()
@@ -77,7 +86,7 @@ fn extrapolate_backwards_sum(lines: &Vec<Vec<i64>>) -> i64 {
         sum += last_interpolated;
 
         for (i, l) in rewrite_lines.into_iter().rev().enumerate() {
-            println!("{}{l}", (0..=i).map(|_| " ").collect::<Vec<_>>().join(""));
+            trace!("{}{l}", (0..=i).map(|_| " ").collect::<Vec<_>>().join(""));
         }
     }
 
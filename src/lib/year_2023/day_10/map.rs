@@ -1,6 +1,7 @@
 use crate::{
-    neighbor_map::{Map as NMap, Neighbors, NodeData},
+    neighbor_map::{Map as NMap, Neighborhood, Neighbors, Node, Topology},
     prelude::*,
+    search,
 };
 use std::{collections::HashMap, fmt};
 
@@ -94,7 +95,13 @@ impl FromStr for Map {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let m = NMap::<Tile>::new(data.get(0).unwrap().len(), data.len());
+        // diagonals are not possible
+        let mut m = NMap::<Tile>::with_neighborhood(
+            data.get(0).unwrap().len(),
+            data.len(),
+            Neighborhood::VonNeumann,
+            Topology::Bounded,
+        )?;
 
         // populate m by zipping it together with ourself
         let data_source = data.into_iter().rev().enumerate().flat_map(|(y, row)| {
@@ -102,38 +109,28 @@ impl FromStr for Map {
                 .enumerate()
                 .map(move |(x, cell)| ((x, y), cell))
         });
-        for (((xd, yd), mut dest), ((xs, ys), src)) in m.iter_mut().zip(data_source) {
+        for (((xd, yd), dest), ((xs, ys), src)) in m.iter_mut().zip(data_source) {
             if xd != xs {
                 bail!("when iterating the source data, expected x={xs} to be x={xd}; is the dataset square?");
             } else if ys != yd {
                 bail!("when iterating the source data, expected x={ys} to be x={yd}; is the dataset square?");
             }
 
-            dest.set(src);
-        }
-
-        // finally, clear neighbors and map them over to traversible tiles
-        for (_, mut tile) in m.iter_mut() {
-            let n = tile.neighbors_mut();
-            // diagonals are not possible
-            n.upleft = None;
-            n.upright = None;
-            n.downleft = None;
-            n.downright = None;
+            *dest = src;
         }
 
         Ok(Self(m))
     }
 }
 
-pub trait MainLoopNeighbors {
+pub trait MainLoopNeighbors<'a> {
     // returns neighbors which are a valid part of the main loop
-    fn main_loop_neighbors(&self) -> Neighbors<Tile>;
+    fn main_loop_neighbors(&self) -> Neighbors<'a, Tile>;
 }
 
-impl MainLoopNeighbors for NodeData<Tile> {
-    fn main_loop_neighbors(&self) -> Neighbors<Tile> {
-        let mut n = self.neighbors().to_owned();
+impl<'a> MainLoopNeighbors<'a> for Node<'a, Tile> {
+    fn main_loop_neighbors(&self) -> Neighbors<'a, Tile> {
+        let mut n = self.neighbors();
         use Tile::*;
         match self.value() {
             Empty => {
@@ -167,19 +164,19 @@ impl MainLoopNeighbors for NodeData<Tile> {
             Start => {
                 // depends on the destination tile's type and its relative
                 // direction to the start.
-                match n.left.as_ref().map(|nt| *nt.borrow().value()) {
+                match n.left.as_ref().map(|nt| *nt.value()) {
                     Some(BottomLeftCorner | TopRightCorner | Horizontal) => (),
                     _ => {
                         n.left = None;
                     }
                 }
-                match n.right.as_ref().map(|nt| *nt.borrow().value()) {
+                match n.right.as_ref().map(|nt| *nt.value()) {
                     Some(BottomRightCorner | TopRightCorner | Horizontal) => (),
                     _ => {
                         n.right = None;
                     }
                 }
-                match n.up.as_ref().map(|nt| *nt.borrow().value()) {
+                match n.up.as_ref().map(|nt| *nt.value()) {
                     Some(
                         BottomRightCorner | BottomLeftCorner | TopLeftCorner | TopRightCorner
                         | Vertical,
@@ -188,7 +185,7 @@ impl MainLoopNeighbors for NodeData<Tile> {
                         n.up = None;
                     }
                 }
-                match n.down.as_ref().map(|nt| *nt.borrow().value()) {
+                match n.down.as_ref().map(|nt| *nt.value()) {
                     Some(
                         BottomRightCorner | BottomLeftCorner | TopLeftCorner | TopRightCorner
                         | Vertical,
@@ -204,6 +201,22 @@ impl MainLoopNeighbors for NodeData<Tile> {
     }
 }
 
+// tiles are searched along the main loop, a step at a time
+impl search::Graph for Map {
+    type Node = Coords;
+
+    fn neighbors(&self, &(x, y): &Coords) -> Vec<(Coords, u64)> {
+        let Ok(node) = self.get(x, y) else {
+            return vec![];
+        };
+
+        node.main_loop_neighbors()
+            .iter()
+            .map(|n| ((n.x(), n.y()), 1))
+            .collect()
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct DistanceMap {
     pub data: HashMap<Coords, usize>,
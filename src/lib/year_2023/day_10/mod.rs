@@ -1,6 +1,6 @@
 mod map;
 
-use crate::prelude::*;
+use crate::{prelude::*, search};
 use map::{Coords, DistanceMap, Map, Steps, TileKind, TileKindMap};
 use std::collections::HashSet;
 
@@ -8,24 +8,37 @@ use crate::year_2023::day_10::map::MainLoopNeighbors;
 
 use self::map::Tile;
 
-pub fn run(input: String) -> Result<()> {
-    let m: Map = input.parse()?;
-    println!("map:\n{m}");
-    let dm = DepthFirstVisited::distance_map(&m);
+pub struct Solution;
 
-    println!("depth map:\n{dm}");
+impl Solver for Solution {
+    type Input = Map;
 
-    let (coords, n) = DepthFirstVisited::furthest_point(&m);
-    println!("furthest: {coords:?} with {n} steps");
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part1(m: &Self::Input) -> Result<Answer> {
+        debug!("map:\n{m}");
+        let dm = BreadthFirst::distance_map(m);
+
+        debug!("depth map:\n{dm}");
+
+        let (coords, n) = BreadthFirst::furthest_point(m);
+        debug!("furthest: {coords:?}");
 
-    let imap = m.interior_space_map();
-    println!("{imap}");
-    let interior_nodes = m.interior_spaces();
-    println!("interior node count: {interior_nodes}");
+        Ok(n.into())
+    }
+
+    fn part2(m: &Self::Input) -> Result<Answer> {
+        let imap = m.interior_space_map();
+        debug!("interior spaces:\n{imap}");
 
-    Ok(())
+        Ok(m.interior_spaces().into())
+    }
 }
 
+register!(2023, 10, Solution);
+
 trait FurthestPoint {
     fn distance_map(map: &Map) -> DistanceMap;
 
@@ -39,41 +52,15 @@ trait FurthestPoint {
     }
 }
 
-struct DepthFirstVisited;
+struct BreadthFirst;
 
-impl FurthestPoint for DepthFirstVisited {
+impl FurthestPoint for BreadthFirst {
     fn distance_map(map: &Map) -> DistanceMap {
-        let mut visited = DistanceMap {
+        let costs = search::bfs_all(map, map.start());
+        DistanceMap {
+            data: costs.iter().map(|(c, d)| (*c, d as usize)).collect(),
             height: map.height(),
             width: map.width(),
-            ..Default::default()
-        };
-
-        Self::distance_map_inner(&mut visited, map, map.start().unwrap(), 0);
-        visited
-    }
-}
-
-impl DepthFirstVisited {
-    fn distance_map_inner(
-        // coords => minimum distance to reach
-        visited: &mut DistanceMap,
-        map: &Map,
-        current: Coords,
-        distance: usize,
-    ) {
-        if visited.get(&current).is_some_and(|c| *c < distance) {
-            return;
-        }
-        visited.insert(current, distance);
-
-        let node = map.get(current.0, current.1).unwrap().to_owned();
-        let neighbors = node.main_loop_neighbors();
-
-        for n in neighbors.iter() {
-            let n = n.borrow();
-
-            DepthFirstVisited::distance_map_inner(visited, map, (n.x(), n.y()), distance + 1);
         }
     }
 }
@@ -110,7 +97,7 @@ impl InteriorSpace for Map {
         // None values in the direction map.
         let faces = MainPipeLoopFaces(faces);
 
-        let mut tkmap = DepthFirstVisited::distance_map(self)
+        let mut tkmap = BreadthFirst::distance_map(self)
             .iter()
             .map(|(coord, _)| {
                 (
@@ -186,19 +173,19 @@ impl Map {
         let mut path = Vec::<Coords>::new();
 
         let cur = self.start().unwrap();
-        let mut cur = self.get(cur.0, cur.1).unwrap().to_owned();
-        let mut last = cur.clone();
+        let mut cur = self.get(cur.0, cur.1).unwrap();
+        let mut last = cur;
         loop {
             path.push((cur.x(), cur.y()));
 
             let mut done = true;
             for n in cur.main_loop_neighbors().iter() {
-                let ncoord = (n.borrow().x(), n.borrow().y());
+                let ncoord = (n.x(), n.y());
                 if ncoord == (last.x(), last.y()) || ncoord == *path.first().unwrap() {
                     continue;
                 }
                 last = cur;
-                cur = n.borrow().to_owned();
+                cur = n;
                 done = false;
                 break;
             }
@@ -306,11 +293,11 @@ mod tests {
     fn test_furthest_example_1() {
         let m: Map = EXAMPLE_1.parse().unwrap();
 
-        let (furthest, distance) = DepthFirstVisited::furthest_point(&m);
+        let (furthest, distance) = BreadthFirst::furthest_point(&m);
         assert_eq!(4, distance);
         assert_eq!((3, 1), furthest);
 
-        let dm = DepthFirstVisited::distance_map(&m);
+        let dm = BreadthFirst::distance_map(&m);
         println!("{m}");
         println!("{dm}");
 
@@ -328,11 +315,11 @@ mod tests {
     fn test_furthest_example_2() {
         let m: Map = EXAMPLE_2.parse().unwrap();
 
-        let (furthest, distance) = DepthFirstVisited::furthest_point(&m);
+        let (furthest, distance) = BreadthFirst::furthest_point(&m);
         assert_eq!(8, distance);
         assert_eq!((4, 2), furthest);
 
-        let dm = DepthFirstVisited::distance_map(&m);
+        let dm = BreadthFirst::distance_map(&m);
         println!("{m}");
         println!("{dm}");
 
@@ -351,11 +338,11 @@ mod tests {
         let m: Map = CUSTOM_1.parse().unwrap();
         println!("{m}");
 
-        let (furthest, distance) = DepthFirstVisited::furthest_point(&m);
+        let (furthest, distance) = BreadthFirst::furthest_point(&m);
         assert_eq!(6, distance);
         assert!(furthest == (2, 0) || furthest == (2, 4));
 
-        let dm = DepthFirstVisited::distance_map(&m);
+        let dm = BreadthFirst::distance_map(&m);
         println!("{dm}");
 
         assert_eq!(
@@ -1,4 +1,4 @@
-use crate::{coord::Coordinate, prelude::*};
+use crate::{coord::Coordinate, grid::Grid, prelude::*};
 use std::{collections::HashSet, fmt};
 
 pub type Coord = Coordinate<usize>;
@@ -132,36 +132,30 @@ impl fmt::Display for Map {
     }
 }
 
-// assumes retangular input
 impl FromStr for Map {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let s = s.trim();
-        let width = s
-            .lines()
-            .find(|l| !l.is_empty())
-            .map(|l| l.trim().split("").filter(|c| !c.is_empty()).count())
-            .unwrap_or_default();
-        let height = s.lines().filter(|l| !l.is_empty()).count();
-        let data = s
+        // lines are trimmed so that examples can be indented
+        let s = s
             .trim()
             .lines()
-            .enumerate()
-            .flat_map(|(y, l)| {
-                l.trim()
-                    .split("")
-                    .filter(|v| !v.is_empty())
-                    .enumerate()
-                    .filter(|(_, v)| *v == "#")
-                    .map(move |(x, _)| Coord { x, y })
-            })
-            .collect();
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let grid = Grid::parse(&s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => bail!("unknown tile '{c}'"),
+        })?;
 
         Ok(Self {
-            width,
-            height,
-            data,
+            width: grid.width(),
+            height: grid.height(),
+            data: grid
+                .positions(|g| *g)
+                .map(|(x, y)| Coord { x, y })
+                .collect(),
         })
     }
 }
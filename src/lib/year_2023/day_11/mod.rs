@@ -5,19 +5,34 @@ use itertools::Itertools;
 
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    let mut m: Map = input.parse()?;
-    m.expand_count(1);
+pub struct Solution;
 
-    println!("minimum distance sum, doubled: {}", m.min_distance_sum());
+impl Solver for Solution {
+    type Input = String;
 
-    let mut m: Map = input.parse()?;
-    m.expand_count(1_000_000 - 1);
+    // expansion mutates the map, so each part parses its own copy
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse::<Map>()?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let mut m: Map = input.parse()?;
+        m.expand_count(1);
 
-    println!("minimum distance sum, 1MMx: {}", m.min_distance_sum());
-    Ok(())
+        Ok(m.min_distance_sum().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let mut m: Map = input.parse()?;
+        m.expand_count(1_000_000 - 1);
+
+        Ok(m.min_distance_sum().into())
+    }
 }
 
+register!(2023, 11, Solution);
+
 trait ShortestPath {
     fn shortest_path(&self, from: Coord, to: Coord) -> usize;
 }
@@ -47,7 +62,7 @@ impl ShortestPath for Brute {
                 (from.x, from.y - 1)
             };
             from = Coord { x: new.0, y: new.1 };
-            points.push(from.clone());
+            points.push(from);
         }
 
         points.len()
@@ -75,22 +90,11 @@ impl Map {
                 let lg = combos.pop().unwrap();
                 (lg.0, rg.0)
             })
-            .map(|(lg, rg)| lg.l1_norm(&rg))
+            .map(|(lg, rg)| lg.manhattan(&rg))
             .sum()
     }
 }
 
-trait L1Norm {
-    fn l1_norm(&self, other: &Self) -> usize;
-}
-
-impl L1Norm for Coord {
-    fn l1_norm(&self, other: &Self) -> usize {
-        (other.x as isize - self.x as isize).unsigned_abs()
-            + (other.y as isize - self.y as isize).unsigned_abs()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,9 +138,9 @@ mod tests {
         let galaxies = m.galaxies().map(|g| g.0).collect::<Vec<_>>();
 
         assert_eq!(9, galaxies.len());
-        assert_eq!(9, galaxies[4].l1_norm(&galaxies[8]));
-        assert_eq!(15, galaxies[0].l1_norm(&galaxies[6]));
-        assert_eq!(17, galaxies[2].l1_norm(&galaxies[5]));
+        assert_eq!(9, galaxies[4].manhattan(&galaxies[8]));
+        assert_eq!(15, galaxies[0].manhattan(&galaxies[6]));
+        assert_eq!(17, galaxies[2].manhattan(&galaxies[5]));
 
         assert_eq!(374, m.min_distance_sum());
     }
@@ -1,7 +1,5 @@
 mod rows;
 
-use std::io::Write;
-
 use crate::prelude::*;
 use itertools::{EitherOrBoth, Itertools};
 use rows::*;
@@ -9,28 +7,37 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-pub fn run(input: String) -> Result<()> {
-    let mut rows: Rows = input.parse()?;
+pub struct Solution;
 
-    // println!("total combinations: {}", rows.total_combinations());
+impl Solver for Solution {
+    type Input = Rows;
 
-    print!("unfolding... ");
-    rows.unfold();
-    println!("done");
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
 
-    let mut sum = 0;
-    for (i, row) in rows.iter().enumerate() {
-        print!("{i}: ");
-        let _ = std::io::stdout().flush();
-        let v = row.combinations(Strategy::Planned);
-        println!("{v}");
-        sum += v;
+    fn part1(rows: &Self::Input) -> Result<Answer> {
+        Ok(rows.total_combinations(Strategy::Planned).into())
     }
 
-    println!("total unfolded combinations: {}", sum);
-    Ok(())
+    fn part2(rows: &Self::Input) -> Result<Answer> {
+        let mut rows = rows.clone();
+
+        rows.unfold();
+
+        let mut sum = 0;
+        for (i, row) in rows.iter().enumerate() {
+            let v = row.combinations(Strategy::Planned);
+            debug!("{i}: {v}");
+            sum += v;
+        }
+
+        Ok(sum.into())
+    }
 }
 
+register!(2023, 12, Solution);
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Strategy {
     // Brute,
@@ -50,12 +57,12 @@ impl RowSpec {
 }
 
 impl Rows {
-    #[allow(dead_code)]
     fn total_combinations(&self, strat: Strategy) -> usize {
         self.iter().map(|rspec| rspec.combinations(strat)).sum()
     }
 }
 
+#[allow(dead_code)]
 struct BruteComboFinder {
     tiles: Vec<Tile>,
     seq: Vec<usize>,
@@ -242,6 +249,7 @@ impl PlannedComboFinder {
     }
 
     // basics--- can this still work out?
+    #[allow(dead_code)]
     fn is_possible(&self) -> bool {
         if !self.seq.is_empty() && self.tiles.is_empty() {
             return false;
@@ -426,7 +434,7 @@ mod tests {
         .map(|(s, c)| (s.parse::<RowSpec>().unwrap(), c))
         .enumerate();
 
-        for (i, (mut row, expected)) in rows {
+        for (i, (row, expected)) in rows {
             println!(
                 "========================\nROW #{}\n========================",
                 i + 1
@@ -101,12 +101,13 @@ impl RowSpec {
             .collect();
     }
 
+    #[allow(dead_code)]
     pub fn count(&self, tile: Tile) -> usize {
         self.tiles.iter().filter(|t| **t == tile).count()
     }
 }
 
-#[derive(Debug, Deref, DerefMut)]
+#[derive(Debug, Clone, Deref, DerefMut)]
 pub struct Rows(Vec<RowSpec>);
 
 impl FromStr for Rows {
@@ -1,9 +1,22 @@
 #![allow(dead_code, unused_imports, unused_variables)]
 use crate::prelude::*;
 
-pub fn run(input: String) -> Result<()> {
-    Ok(())
+pub struct Solution;
+
+impl Solver for Solution {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        bail!("not implemented")
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        bail!("not implemented")
+    }
 }
 
-#[cfg(test)]
-mod tests {}
+// register!(YEAR, DAY, Solution);
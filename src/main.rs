@@ -1,68 +1,519 @@
-use advent_of_code::{prelude::*, year_2022, year_2023};
+use advent_of_code::{
+    client::{Client, Http},
+    config::Config,
+    fixtures, import, inputs,
+    prelude::*,
+    profile::CountingAlloc,
+    runner, scaffold,
+    solver::{self, Puzzle},
+    submit, verify, watch,
+};
 
 use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use structopt::StructOpt;
 
+// counts allocations for --profile
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+// Format is how run results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    // one JSON object per run, see runner::Report
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => bail!("unknown format '{s}', expected text or json"),
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
-#[structopt(
-    name = "advent-of-code",
-    about = "Runs advent of code solvers for a given year and problem provided an input."
-)]
+#[structopt(name = "advent-of-code", about = "Runs advent of code solvers.")]
 struct Opt {
-    /// Year of advent of code problem to run.
-    #[structopt(name = "year")]
-    year: i32,
+    #[structopt(long = "log-level", short = "l", global = true)]
+    log_level: Option<log::LevelFilter>,
+
+    /// Config file, by default $XDG_CONFIG_HOME/advent-of-code/config.toml.
+    #[structopt(long, global = true, parse(from_os_str))]
+    config: Option<PathBuf>,
 
-    /// The problem to run.
-    #[structopt(name = "problem")]
-    problem: i32,
+    #[structopt(subcommand)]
+    cmd: Command,
+}
 
-    /// Input file
-    #[structopt(parse(from_os_str))]
-    input: PathBuf,
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Runs the solver for a given year and problem. Without an input file or
+    /// --input-str, the stored input is used, and fetched first if it's missing and a
+    /// session token is configured.
+    Run {
+        /// Year of advent of code problem to run.
+        #[structopt(name = "year")]
+        year: u32,
 
-    #[structopt(long = "log-level", short = "l")]
-    log_level: Option<log::LevelFilter>,
+        /// The problem to run.
+        #[structopt(name = "problem")]
+        problem: u32,
+
+        /// Input file, or - to read from stdin.
+        #[structopt(parse(from_os_str))]
+        input: Option<PathBuf>,
+
+        /// Input to use instead of a file, e.g. an example.
+        #[structopt(long, conflicts_with = "input")]
+        input_str: Option<String>,
+
+        /// Directory containing inputs as <year>/<day>.txt.
+        #[structopt(long, default_value = inputs::DEFAULT_DIR, parse(from_os_str))]
+        inputs: PathBuf,
+
+        /// Parts to solve: 1, 2, or both.
+        #[structopt(long, default_value = "both")]
+        part: runner::Parts,
+
+        /// Report the time and heap allocations of parsing and each part.
+        #[structopt(long)]
+        profile: bool,
+
+        /// Output format, text or json.
+        #[structopt(long, default_value = "text")]
+        format: Format,
+
+        /// Rerun whenever the input file or the day's fixtures change, showing
+        /// how the answers changed.
+        #[structopt(long)]
+        watch: bool,
+
+        /// Directory containing example fixtures as <year>/<day>/<name>.toml.
+        #[structopt(long, default_value = fixtures::DEFAULT_DIR, parse(from_os_str))]
+        fixtures: PathBuf,
+    },
+    /// Runs every solver against its stored input, printing answers and timings.
+    RunAll {
+        /// Only run problems from this year.
+        #[structopt(long)]
+        year: Option<u32>,
+
+        /// Directory containing inputs as <year>/<day>.txt.
+        #[structopt(long, default_value = inputs::DEFAULT_DIR, parse(from_os_str))]
+        inputs: PathBuf,
+
+        /// Parts to solve: 1, 2, or both.
+        #[structopt(long, default_value = "both")]
+        part: runner::Parts,
+
+        /// Report the time and heap allocations of parsing and each part.
+        #[structopt(long)]
+        profile: bool,
+
+        /// Output format, text or json. JSON is one object per line for each run.
+        #[structopt(long, default_value = "text")]
+        format: Format,
+    },
+    /// Creates a new day from the template and adds it to its year module.
+    New {
+        /// Year of the new problem.
+        #[structopt(name = "year")]
+        year: u32,
+
+        /// The new problem's day.
+        #[structopt(name = "day")]
+        day: u32,
+
+        /// The library source directory.
+        #[structopt(long, default_value = scaffold::DEFAULT_SRC, parse(from_os_str))]
+        src: PathBuf,
+
+        /// Directory containing example fixtures as <year>/<day>/<name>.toml.
+        #[structopt(long, default_value = fixtures::DEFAULT_DIR, parse(from_os_str))]
+        fixtures: PathBuf,
+    },
+    /// Runs every solver against its stored input and checks the results against
+    /// the accepted answers.
+    Verify {
+        /// Only verify problems from this year.
+        #[structopt(long)]
+        year: Option<u32>,
+
+        /// File containing accepted answers.
+        #[structopt(long, default_value = verify::DEFAULT_ANSWERS, parse(from_os_str))]
+        answers: PathBuf,
+
+        /// Directory containing inputs as <year>/<day>.txt.
+        #[structopt(long, default_value = inputs::DEFAULT_DIR, parse(from_os_str))]
+        inputs: PathBuf,
+    },
+    /// Writes the examples and their answers from a saved puzzle page as fixtures.
+    /// Re-import the page after solving part 1 to pick up part 2's answers.
+    Import {
+        /// The puzzle page saved from a browser.
+        #[structopt(name = "page", parse(from_os_str))]
+        page: PathBuf,
+
+        /// Directory containing example fixtures as <year>/<day>/<name>.toml.
+        #[structopt(long, default_value = fixtures::DEFAULT_DIR, parse(from_os_str))]
+        fixtures: PathBuf,
+
+        /// Replace existing fixtures.
+        #[structopt(long)]
+        force: bool,
+    },
+    /// Submits an answer for a part. Without --answer, the part is solved with the
+    /// stored input and its answer is submitted. Answers which earlier guesses show
+    /// are wrong are refused without submitting them.
+    Submit {
+        /// Year of the problem.
+        #[structopt(name = "year")]
+        year: u32,
+
+        /// The problem's day.
+        #[structopt(name = "day")]
+        day: u32,
+
+        /// The part to submit, 1 or 2.
+        #[structopt(name = "part")]
+        part: u8,
+
+        /// Answer to submit instead of solving the part.
+        #[structopt(long)]
+        answer: Option<String>,
+
+        /// Directory containing inputs as <year>/<day>.txt.
+        #[structopt(long, default_value = inputs::DEFAULT_DIR, parse(from_os_str))]
+        inputs: PathBuf,
+
+        /// File recording submitted answers and their verdicts.
+        #[structopt(long, default_value = submit::DEFAULT_HISTORY, parse(from_os_str))]
+        history: PathBuf,
+    },
 }
 
 fn main() -> Result<()> {
     pretty_env_logger::init();
     let opt = Opt::from_args();
-    let input = read_to_string(opt.input).expect("failed to read input file");
 
     if let Some(lvl) = opt.log_level {
         log::set_max_level(lvl);
     }
 
-    match (opt.year, opt.problem) {
-        (2022, 5) => year_2022::day_05::run(input),
-        (2022, 6) => year_2022::day_06::run(input),
-        (2022, 7) => year_2022::day_07::run(input),
-        (2022, 8) => year_2022::day_08::run(input),
-        (2022, 9) => year_2022::day_09::run(input),
-        (2022, 10) => year_2022::day_10::run(input),
-        (2022, 11) => year_2022::day_11::run(input),
-        (2022, 12) => year_2022::day_12::run(input),
-        (2022, 13) => year_2022::day_13::run(input),
-        (2022, 14) => year_2022::day_14::run(input),
-        (2022, 15) => year_2022::day_15::run(input),
-        (2022, 16) => year_2022::day_16::run(input),
-        (2022, 17) => year_2022::day_17::run(input),
-        (2023, 1) => year_2023::day_01::run(input),
-        (2023, 2) => year_2023::day_02::run(input),
-        (2023, 3) => year_2023::day_03::run(input),
-        (2023, 4) => year_2023::day_04::run(input),
-        (2023, 5) => year_2023::day_05::run(input),
-        (2023, 6) => year_2023::day_06::run(input),
-        (2023, 7) => year_2023::day_07::run(input),
-        (2023, 8) => year_2023::day_08::run(input),
-        (2023, 9) => year_2023::day_09::run(input),
-        (2023, 10) => year_2023::day_10::run(input),
-        (2023, 11) => year_2023::day_11::run(input),
-        (2023, 12) => year_2023::day_12::run(input),
-        (2023, _) | (2022, _) => bail!("unknown problem number {}", opt.problem),
-        (_, _) => bail!("unkown year {}", opt.year),
+    match opt.cmd {
+        Command::Run {
+            year,
+            problem,
+            input,
+            input_str,
+            inputs,
+            part,
+            profile,
+            format,
+            watch,
+            fixtures,
+        } => {
+            let puzzle = match solver::get(year, problem) {
+                Some(p) => p,
+                None if solver::years().contains(&year) => {
+                    bail!("unknown problem number {}", problem)
+                }
+                None => bail!("unkown year {}", year),
+            };
+
+            if watch {
+                ensure!(format == Format::Text, "--watch only writes text");
+                let path = match (input, input_str) {
+                    (_, Some(_)) => bail!("--watch needs an input file, not --input-str"),
+                    (Some(path), None) if path == Path::new("-") => {
+                        bail!("--watch can't watch stdin")
+                    }
+                    (Some(path), None) => path,
+                    (None, None) => {
+                        // fetch the input now if it's missing
+                        stored_input(opt.config.as_deref(), &inputs, year, problem)?;
+                        inputs::path(&inputs, year, problem)
+                    }
+                };
+                return watch_day(puzzle, &path, &fixtures, part);
+            }
+
+            let input = match (input, input_str) {
+                (_, Some(s)) => s,
+                (Some(path), None) => read_input_file(&path)?,
+                (None, None) => stored_input(opt.config.as_deref(), &inputs, year, problem)?,
+            };
+
+            run(puzzle, &input, part, format, profile)
+        }
+        Command::RunAll {
+            year,
+            inputs,
+            part,
+            profile,
+            format,
+        } => run_all(year, inputs, part, format, profile),
+        Command::New {
+            year,
+            day,
+            src,
+            fixtures,
+        } => {
+            for path in scaffold::new_day(&src, &fixtures, year, day)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Verify {
+            year,
+            answers,
+            inputs,
+        } => verify(year, answers, inputs),
+        Command::Import {
+            page,
+            fixtures,
+            force,
+        } => {
+            for path in import::import(&page, &fixtures, force)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            inputs,
+            history,
+        } => submit(
+            opt.config.as_deref(),
+            year,
+            day,
+            part,
+            answer,
+            inputs,
+            history,
+        ),
+    }
+}
+
+// reads an input file, or stdin if the path is -
+fn read_input_file(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("failed to read input from stdin")?;
+        return Ok(input);
+    }
+
+    read_to_string(path).context("failed to read input file")
+}
+
+// reads the stored input, fetching it first if a session token is configured
+fn stored_input(config: Option<&Path>, inputs: &Path, year: u32, day: u32) -> Result<String> {
+    let client = Config::load(config)?
+        .session
+        .map(|s| Client::new(Http::new(&s)));
+    let mut store = inputs::Store::new(inputs);
+    if let Some(client) = &client {
+        store = store.with_client(client);
+    }
+
+    store.get(year, day)
+}
+
+fn run(
+    puzzle: &'static Puzzle,
+    input: &str,
+    parts: runner::Parts,
+    format: Format,
+    profile: bool,
+) -> Result<()> {
+    let mut run = runner::run_parts(puzzle, input, parts);
+    if format == Format::Json {
+        println!("{}", serde_json::to_string(&run.report(profile))?);
+        if let Some(e) = run.errors().next() {
+            bail!("{e:#}");
+        }
+        return Ok(());
+    }
+
+    if profile {
+        println!("{}", runner::Profile(std::slice::from_ref(&run)));
+    }
+
+    run.parse.result?;
+    for (part, t) in run.parts.drain(..) {
+        print_answer(part, &t.result?);
+    }
+
+    Ok(())
+}
+
+// reruns the puzzle whenever its input or fixtures change, until interrupted
+fn watch_day(
+    puzzle: &'static Puzzle,
+    input: &Path,
+    fixtures: &Path,
+    parts: runner::Parts,
+) -> Result<()> {
+    let mut w = watch::Watcher::new(puzzle, parts, input, fixtures);
+    println!(
+        "watching {} and {}",
+        input.display(),
+        fixtures::dir(fixtures, puzzle.year, puzzle.day).display()
+    );
+    loop {
+        if let Some(report) = w.poll() {
+            print!("\n{report}");
+        }
+        std::thread::sleep(watch::POLL_INTERVAL);
+    }
+}
+
+// multi-line answers start on their own line so they stay aligned
+fn print_answer(part: u8, answer: &Answer) {
+    if answer.is_multiline() {
+        println!("part {part}:\n{answer}");
+    } else {
+        println!("part {part}: {answer}");
+    }
+}
+
+fn run_all(
+    year: Option<u32>,
+    inputs: PathBuf,
+    parts: runner::Parts,
+    format: Format,
+    profile: bool,
+) -> Result<()> {
+    let mut runs = vec![];
+    let mut missing = vec![];
+    for puzzle in solver::puzzles() {
+        if year.map_or(false, |y| y != puzzle.year) {
+            continue;
+        }
+
+        match read_to_string(inputs::path(&inputs, puzzle.year, puzzle.day)) {
+            Ok(input) => runs.push(runner::run_parts(puzzle, &input, parts)),
+            Err(_) => missing.push(puzzle),
+        }
+    }
+
+    if format == Format::Json {
+        for r in &runs {
+            println!("{}", serde_json::to_string(&r.report(profile))?);
+        }
+        // stdout is only runs, so missing inputs are reported on stderr
+        if !missing.is_empty() {
+            let missing = missing.iter().map(ToString::to_string).collect::<Vec<_>>();
+            eprintln!("missing inputs: {}", missing.join(", "));
+        }
+
+        let failed = runs.iter().filter(|r| r.errors().next().is_some()).count();
+        ensure!(failed == 0, "finished with {failed} failures");
+        return Ok(());
     }
+
+    println!("{}", runner::Table(&runs));
+    if profile {
+        println!("{}", runner::Profile(&runs));
+    }
+
+    for r in &runs {
+        for (part, t) in &r.parts {
+            if let Ok(a) = &t.result {
+                if a.is_multiline() {
+                    println!("{} part {part}:\n{a}\n", r.puzzle);
+                }
+            }
+        }
+    }
+
+    let mut failed = 0;
+    for (r, e) in runs.iter().flat_map(|r| r.errors().map(move |e| (r, e))) {
+        println!("{} failed: {e:#}", r.puzzle);
+        failed += 1;
+    }
+
+    if !missing.is_empty() {
+        let missing = missing.iter().map(ToString::to_string).collect::<Vec<_>>();
+        println!("missing inputs: {}", missing.join(", "));
+    }
+
+    ensure!(failed == 0, "finished with {failed} failures");
+    Ok(())
+}
+
+fn verify(year: Option<u32>, answers: PathBuf, inputs: PathBuf) -> Result<()> {
+    let answers = verify::Answers::load(&answers)?;
+    let reports = verify::verify_all(&inputs, &answers, year);
+
+    let (mut pass, mut fail, mut missing) = (0, 0, 0);
+    for r in &reports {
+        match r.status {
+            verify::Status::Pass => pass += 1,
+            _ if r.status.is_failure() => fail += 1,
+            _ => missing += 1,
+        }
+        println!("{r}");
+    }
+
+    println!("\n{pass} passed, {fail} failed, {missing} missing");
+    ensure!(fail == 0, "{fail} parts failed verification");
+
+    Ok(())
+}
+
+fn submit(
+    config: Option<&Path>,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: Option<String>,
+    inputs: PathBuf,
+    history: PathBuf,
+) -> Result<()> {
+    let session = Config::load(config)?
+        .session
+        .context("submitting requires a session token in the config")?;
+    let client = Client::new(Http::new(&session));
+
+    let answer = match answer {
+        Some(a) => a.parse()?,
+        None => {
+            let puzzle =
+                solver::get(year, day).ok_or_else(|| anyhow!("no solver for {year} day {day}"))?;
+            let input = inputs::Store::new(inputs)
+                .with_client(&client)
+                .get(year, day)?;
+            let parsed = puzzle.parse(&input)?;
+            match part {
+                1 => puzzle.part1(&parsed)?,
+                2 => puzzle.part2(&parsed)?,
+                _ => bail!("part must be 1 or 2, not {part}"),
+            }
+        }
+    };
+    print_answer(part, &answer);
+
+    let mut history = submit::History::load(&history)?;
+    let resp = submit::submit(&client, &mut history, year, day, part, &answer)?;
+
+    println!("{:?}: {}", resp.verdict, resp.message);
+    if let Some(wait) = resp.wait {
+        println!("next submission allowed in {}s", wait.as_secs());
+    }
+
+    Ok(())
 }